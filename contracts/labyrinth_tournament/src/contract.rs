@@ -9,14 +9,31 @@ use self::state::LabyrinthState;
 use labyrinth_tournament::{
     Difficulty, Tournament, TournamentStatus, Player, TournamentPlayer,
     GameRun, LeaderboardEntry, TournamentReward, Operation, Response,
-    InitializationArgument, Message,
+    InitializationArgument, Message, MessageOrigin, AchievementId, PlayerAchievement, ACHIEVEMENTS,
+    Season, SeasonStatus, SeasonStanding, SeasonReward, SEASON_CARRYOVER_PERCENT,
+    season_points_for_rank, placement_achievements, RatingEntry, glicko_update, DEFAULT_RATING,
+    DEFAULT_RATING_DEVIATION, PROVISIONAL_RATING_DEVIATION,
+    Duel, DuelRun, DuelStatus, MIN_DUEL_HOURS, MAX_DUEL_HOURS, DUEL_SEED_DOMAIN,
+    Clan, ClanMember, ClanRole, TeamMode, TeamLeaderboardEntry, MAX_CLAN_MEMBERS,
+    MIN_CLAN_TAG_LEN, MAX_CLAN_TAG_LEN,
+    validate_splits, cleared_splits, upsert_split_entry,
+    Ghost, MAX_GHOST_BYTES, GHOST_LEADERBOARD_SLOTS,
+    RunCommitment, RunTicket, MAX_OPEN_RUN_TICKETS,
+    SeedMode, CancelXpPolicy, maze_path_length, normalize_time,
+    TournamentUpdate, TournamentAmendment, AmendedField,
+    TemplateInput, TournamentTemplate, TournamentParams, DuelRunSubmission, DEFAULT_REWARD_PERCENTAGES, validate_reward_curve,
+    RunReceipt, ReceiptStatus, ClanXpCredit, RunSubmission, RunResult, MAX_BATCH_RUNS,
+    LeaderboardChange, leaderboard_changes, apply_leaderboard_changes, MAX_LEADERBOARD_SUBSCRIBERS,
+    MAX_SEED_SECRET_BYTES, compute_seed_entropy, compute_seed_commitment, derive_maze_seed,
+    schema::{MigrationCursor, Versioned, VersionedRecord, CURRENT_SCHEMA_VERSION, MIGRATION_TABLES},
 };
 use linera_sdk::{
-    linera_base_types::AccountOwner,
+    linera_base_types::{AccountOwner, ChainId, Timestamp},
     abi::WithContractAbi,
-    views::{RootView, View},
+    views::{MapView, RootView, View, ViewError},
     Contract, ContractRuntime,
 };
+use serde::{de::DeserializeOwned, Serialize};
 
 pub struct LabyrinthTournamentContract {
     state: LabyrinthState,
@@ -45,51 +62,28 @@ impl Contract for LabyrinthTournamentContract {
     async fn instantiate(&mut self, argument: Self::InstantiationArgument) {
         // Store the hub chain ID for cross-chain messaging
         self.state.hub_chain_id.set(Some(argument.hub_chain_id));
+
+        // A configured hub other than this chain only learns its role from us
+        let hub_chain = self.hub_chain();
+        if hub_chain != self.runtime.chain_id() {
+            self.runtime.send_message(hub_chain, Message::SetHub { hub_chain_id: hub_chain, epoch: 0, retired: Vec::new() });
+        }
         
         // Initialize counters
-        self.state.next_tournament_id.set(2); // Start at 2 since we create tournament 1
+        self.state.next_tournament_id.set(1);
         self.state.next_run_id.set(1);
+        self.state.next_season_id.set(1);
+        self.state.next_duel_id.set(1);
+        self.state.next_clan_id.set(1);
+        self.state.next_ticket_id.set(1);
+        self.state.next_template_id.set(1);
+        self.state.schema_version.set(CURRENT_SCHEMA_VERSION);
         self.state.recent_runs.set(Vec::new());
         
-        // =====================================================================
-        // AUTO-CREATE 15-DAY TOURNAMENT AT DEPLOYMENT
-        // Like Linera-Arcade: Everyone sees the SAME tournament immediately
-        // No waiting, no manual creation required
-        // =====================================================================
-        let now = self.runtime.system_time();
-        
-        // Tournament duration: 15 days in microseconds
-        const DURATION_DAYS: u64 = 15;
-        let duration_micros = DURATION_DAYS * 24 * 60 * 60 * 1_000_000;
-        let end_time = linera_sdk::linera_base_types::Timestamp::from(
-            now.micros() + duration_micros
-        );
-        
-        // Deterministic seed from deployment timestamp (everyone gets same maze)
-        let maze_seed = format!("labyrinth_legends_{}", now.micros() / 1_000_000);
-        
-        let tournament = Tournament {
-            id: 1,
-            title: "Labyrinth Legends Championship".to_string(),
-            description: "15-day tournament - Navigate the maze faster than anyone else!".to_string(),
-            maze_seed,
-            difficulty: Difficulty::Medium,
-            start_time: now,
-            end_time,
-            status: TournamentStatus::Active,
-            participant_count: 0,
-            total_runs: 0,
-            xp_reward_pool: 10000, // 10,000 XP pool for top players
-            created_at: now,
-        };
-        
-        // Store tournament in BOTH places:
-        // 1. MapView for historical lookup
-        // 2. RegisterView for direct mutation (CRITICAL for state persistence)
-        self.state.tournaments.insert(&1u64, tournament.clone()).unwrap();
-        self.state.active_tournament.set(Some(tournament));
-        self.state.leaderboards.insert(&1u64, Vec::new()).unwrap();
-        self.state.active_tournament_id.set(Some(1));
+        // Everyone sees the same tournament immediately: the default championship
+        // template spawns its first instance (tournament 1) right away
+        self.seed_default_template();
+        self.rotate_tournaments().await;
     }
 
     async fn execute_operation(&mut self, operation: Operation) -> Response {
@@ -99,27 +93,205 @@ impl Contract for LabyrinthTournamentContract {
             None => return Response::Error { message: "Not authenticated".to_string() },
         };
 
+        // Lazy finalization: the first operation after end_time finalizes the active tournament
+        if let Some(active_id) = *self.state.active_tournament_id.get() {
+            self.finalize_if_expired(active_id).await;
+        }
+
+        // Tournament admin and reward claims run on the hub, which owns player and
+        // reward state (and forwards changes to the tournament's shard)
+        if let Some(tournament_id) = operation.hub_tournament_id() {
+            if !self.is_hub() {
+                let hub = self.hub_chain();
+                return Response::Error { message: format!("Tournament {} is administered on the hub chain {}", tournament_id, hub) };
+            }
+        }
+
         match operation {
             Operation::RegisterPlayer { wallet_address, username } => {
                 self.register_player(signer, wallet_address, username).await
             }
             
-            Operation::SubmitRun { tournament_id, time_ms, score, coins, deaths, completed } => {
-                self.submit_run(signer, tournament_id, time_ms, score, coins, deaths, completed).await
+            Operation::SubmitRun { run } => {
+                self.submit_run(signer, run).await
+            }
+
+            Operation::SubmitRuns { runs } => {
+                self.submit_runs(signer, runs).await
+            }
+
+            Operation::StartRun { tournament_id, nonce } => {
+                self.start_run(signer, tournament_id, nonce).await
+            }
+
+            Operation::CommitRun { tournament_id, commitment } => {
+                self.commit_run(signer, tournament_id, commitment).await
+            }
+
+            Operation::SubscribePlayerSnapshots { tournament_id } => {
+                match self.resolve_player(signer).await {
+                    Ok((wallet, _)) => {
+                        self.send_to_hub(Message::SubscribeSnapshots { wallet_address: wallet, tournament_id });
+                        Response::Ok
+                    }
+                    Err(response) => response,
+                }
+            }
+
+            Operation::UnsubscribePlayerSnapshots => {
+                match self.resolve_player(signer).await {
+                    Ok((wallet, _)) => {
+                        self.send_to_hub(Message::UnsubscribeSnapshots { wallet_address: wallet });
+                        Response::Ok
+                    }
+                    Err(response) => response,
+                }
+            }
+
+            Operation::SubscribeLeaderboard { tournament_id } => {
+                let host = self.tournament_chain(tournament_id).await;
+                self.runtime.send_message(host, Message::SubscribeLeaderboard { tournament_id });
+                Response::Ok
+            }
+
+            Operation::UnsubscribeLeaderboard { tournament_id } => {
+                let host = self.tournament_chain(tournament_id).await;
+                self.runtime.send_message(host, Message::UnsubscribeLeaderboard { tournament_id });
+                Response::Ok
             }
             
-            Operation::CreateTournament { title, description, maze_seed, difficulty, duration_days, xp_reward_pool } => {
-                self.create_tournament(signer, title, description, maze_seed, difficulty, duration_days, xp_reward_pool).await
+            Operation::CreateTournament { title, description, seed_commitment, difficulty, duration_days, xp_reward_pool, team_mode, team_best_n, commit_reveal_required, seed_mode, maze_config, reward_percentages } => {
+                let Some(duration_micros) = duration_days.checked_mul(24 * 60 * 60 * 1_000_000) else {
+                    return Response::Error { message: "Tournament duration is too long".to_string() };
+                };
+                let params = TournamentParams {
+                    title,
+                    description,
+                    seed_commitment,
+                    difficulty,
+                    duration_micros,
+                    xp_reward_pool,
+                    team_mode,
+                    team_best_n,
+                    commit_reveal_required,
+                    seed_mode,
+                    maze_config,
+                    reward_percentages,
+                    template_id: None,
+                };
+                self.create_tournament(Some(signer), params).await
+            }
+
+            Operation::CreateTemplate { template } => {
+                self.create_template(template).await
+            }
+
+            Operation::SetTemplateActive { template_id, active } => {
+                self.set_template_active(template_id, active).await
+            }
+
+            Operation::RotateTournaments => {
+                self.rotate_tournaments().await
             }
             
+            Operation::UpdateTournament { tournament_id, update } => {
+                self.update_tournament(signer, tournament_id, update).await
+            }
+
+            Operation::CancelTournament { tournament_id, xp_policy } => {
+                self.cancel_tournament(signer, tournament_id, xp_policy).await
+            }
+
+            Operation::RevealTournamentSeed { tournament_id, secret } => {
+                self.reveal_tournament_seed(tournament_id, secret).await
+            }
+
             Operation::EndTournament { tournament_id } => {
                 self.end_tournament(signer, tournament_id).await
             }
             
             Operation::ClaimReward { tournament_id } => {
+                self.finalize_if_expired(tournament_id).await;
                 self.claim_reward(signer, tournament_id).await
             }
+
+            Operation::ReconcileTournaments => {
+                self.reconcile_tournaments().await
+            }
+
+            Operation::FinalizeExpired { limit } => {
+                self.finalize_expired(limit).await
+            }
+
+            Operation::Migrate { limit } => {
+                self.migrate(limit).await
+            }
+
+            Operation::MigrateHub { hub_chain_id } => {
+                self.migrate_hub(hub_chain_id).await
+            }
+
+            Operation::AddShard { chain_id } => {
+                self.update_shards(chain_id, true).await
+            }
+
+            Operation::RemoveShard { chain_id } => {
+                self.update_shards(chain_id, false).await
+            }
+
+            Operation::LocateTournament { tournament_id } => {
+                self.send_to_hub(Message::LocateTournament { tournament_id });
+                Response::Ok
+            }
             
+            Operation::CreateSeason { title, duration_days, xp_reward_pool } => {
+                self.create_season(title, duration_days, xp_reward_pool).await
+            }
+
+            Operation::EndSeason { season_id } => {
+                self.end_season(signer, season_id).await
+            }
+
+            Operation::ClaimSeasonReward { season_id } => {
+                self.claim_season_reward(signer, season_id).await
+            }
+
+            Operation::CreateClan { name, tag } => {
+                self.create_clan(signer, name, tag).await
+            }
+
+            Operation::InviteToClan { invitee } => {
+                self.clan_action(signer, |wallet, _| Message::InviteToClan { wallet_address: wallet, invitee }).await
+            }
+
+            Operation::JoinClan { clan_id } => {
+                self.clan_action(signer, |wallet, username| Message::JoinClan { wallet_address: wallet, username, clan_id }).await
+            }
+
+            Operation::LeaveClan => {
+                self.clan_action(signer, |wallet, _| Message::LeaveClan { wallet_address: wallet }).await
+            }
+
+            Operation::SetClanRole { member, role } => {
+                self.clan_action(signer, |wallet, _| Message::SetClanRole { wallet_address: wallet, member, role }).await
+            }
+
+            Operation::CreateDuel { opponent, difficulty, duration_hours } => {
+                self.create_duel(signer, opponent, difficulty, duration_hours).await
+            }
+
+            Operation::AcceptDuel { duel_id } => {
+                self.accept_duel(signer, duel_id).await
+            }
+
+            Operation::SubmitDuelRun { run } => {
+                self.submit_duel_run(signer, run).await
+            }
+
+            Operation::ResolveDuel { duel_id } => {
+                self.resolve_duel(duel_id).await
+            }
+
             Operation::BootstrapTournament => {
                 self.bootstrap_tournament().await
             }
@@ -132,529 +304,3113 @@ impl Contract for LabyrinthTournamentContract {
         // NEVER use get_mut() on RegisterView - it does NOT persist state!
         // ALWAYS use: let mut val = view.get().clone(); ... view.set(Some(val));
         // =====================================================================
+
+        // A tracked message the hub chain refused comes back here: nothing to apply
+        if self.runtime.message_is_bouncing() == Some(true) {
+            let reason = "Message rejected by the hub chain".to_string();
+            match message {
+                Message::ApplyRun { receipt_id, .. } => {
+                    self.resolve_receipt(receipt_id, ReceiptStatus::Bounced, None, 0, None, Some(reason)).await;
+                }
+                Message::ApplyRuns { first_receipt_id, runs, .. } if first_receipt_id > 0 => {
+                    for receipt_id in first_receipt_id..first_receipt_id + runs.len() as u64 {
+                        self.resolve_receipt(receipt_id, ReceiptStatus::Bounced, None, 0, None, Some(reason.clone())).await;
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        // Messages addressed to user chains
+        let origin = self.runtime.message_origin_chain_id();
         match message {
-            Message::ApplyRun {
-                wallet_address,
-                username,
-                tournament_id,
-                time_ms,
-                score,
-                coins,
-                deaths,
-                completed,
-            } => {
+            Message::SetHub { hub_chain_id, epoch, retired } => {
+                self.set_hub(hub_chain_id, epoch, retired).await;
+                return;
+            }
+            Message::RunAccepted { receipt_id, run_id, xp_earned, rank } => {
+                if self.receipt_from_host(receipt_id, origin).await {
+                    self.resolve_receipt(receipt_id, ReceiptStatus::Confirmed, Some(run_id), xp_earned, rank, None).await;
+                }
+                return;
+            }
+            Message::RunRejected { receipt_id, reason } => {
+                if self.receipt_from_host(receipt_id, origin).await {
+                    self.resolve_receipt(receipt_id, ReceiptStatus::Rejected, None, 0, None, Some(reason)).await;
+                }
+                return;
+            }
+            Message::RunsApplied { first_receipt_id, results, rank } => {
+                for (receipt_id, result) in (first_receipt_id..).zip(results) {
+                    if !self.receipt_from_host(receipt_id, origin).await {
+                        continue;
+                    }
+                    match result.run_id {
+                        Some(run_id) => {
+                            self.resolve_receipt(receipt_id, ReceiptStatus::Confirmed, Some(run_id), result.xp_earned, rank, None).await;
+                        }
+                        None => {
+                            self.resolve_receipt(receipt_id, ReceiptStatus::Rejected, None, 0, None, result.reason).await;
+                        }
+                    }
+                }
+                return;
+            }
+            Message::LeaderboardDelta { tournament_id, changes, removed, reset } => {
+                // The tournament's own chain is authoritative - never patch it from a copy
+                let host = self.tournament_chain(tournament_id).await;
+                if origin == Some(host) && host != self.runtime.chain_id() {
+                    let mut board = if reset {
+                        Vec::new()
+                    } else {
+                        self.state.leaderboards.get(&tournament_id).await.ok().flatten().unwrap_or_default()
+                    };
+                    apply_leaderboard_changes(&mut board, changes, &removed);
+                    let now = self.runtime.system_time();
+                    let _ = self.state.leaderboards.insert(&tournament_id, board);
+                    let _ = self.state.leaderboard_synced_at.insert(&tournament_id, now);
+                    self.bump_leaderboard_version(tournament_id).await;
+                }
+                return;
+            }
+            Message::HostTournament { tournament } => {
+                if origin == Some(self.hub_chain()) {
+                    self.host_tournament(*tournament).await;
+                }
+                return;
+            }
+            Message::TournamentLocation { tournament_id, chain_id } => {
+                if origin == Some(self.hub_chain()) && !self.is_hub() {
+                    let _ = self.state.tournament_shards.insert(&tournament_id, chain_id);
+                }
+                return;
+            }
+            Message::PlayerSnapshot { wallet_address, player, tournament, tournament_player, rank } => {
+                // The hub's own state is authoritative - never overwrite it with a copy
+                if origin == Some(self.hub_chain()) && !self.is_hub() {
+                    let tournament = tournament.map(|t| *t);
+                    let tournament_player = tournament_player.map(|tp| *tp);
+                    self.apply_snapshot(wallet_address, *player, tournament, tournament_player, rank).await;
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        // Everything else is bound for the tournament's chain (a shard, or the hub)
+        // or for the hub, and is only applied there - only from user chains
+        let host = match message.tournament_id() {
+            Some(tournament_id) => self.tournament_chain(tournament_id).await,
+            None => self.hub_chain(),
+        };
+        if host != self.runtime.chain_id() {
+            // Stale routing on the sender: tell it where to go (the message itself is dropped)
+            if let Some(origin) = origin {
+                // The sender still routes here, so it accepts this rejection before the redirect
+                let reason = "Not this tournament's chain - resubmit the run".to_string();
+                match &message {
+                    Message::ApplyRun { receipt_id, .. } if *receipt_id > 0 => {
+                        self.runtime.send_message(origin, Message::RunRejected { receipt_id: *receipt_id, reason });
+                    }
+                    Message::ApplyRuns { first_receipt_id, runs, .. } if *first_receipt_id > 0 => {
+                        let results = vec![RunResult::rejected(reason); runs.len()];
+                        let first_receipt_id = *first_receipt_id;
+                        self.runtime.send_message(origin, Message::RunsApplied { first_receipt_id, results, rank: None });
+                    }
+                    _ => {}
+                }
+                match message.tournament_id() {
+                    Some(tournament_id) if self.is_hub() => {
+                        self.runtime.send_message(origin, Message::TournamentLocation { tournament_id, chain_id: host });
+                    }
+                    _ => {
+                        let update = self.hub_update().await;
+                        self.runtime.send_message(origin, update);
+                    }
+                }
+            }
+            return;
+        }
+        let Some(sender) = origin else { return };
+        if !self.origin_allowed(&message, sender).await {
+            return;
+        }
+        let _ = self.state.user_chains.insert(&sender, self.runtime.system_time());
+
+        // Lazy finalization: a message that observes an overdue tournament finalizes it
+        if let Some(tournament_id) = message.tournament_id() {
+            self.finalize_if_expired(tournament_id).await;
+        }
+
+        match message {
+            Message::ApplyRun { wallet_address, username, run, receipt_id } => {
+                let tournament_id = run.tournament_id;
+                let (mut results, rank) = self.apply_runs(wallet_address, username, tournament_id, vec![run]).await;
+
+                // Delivery receipt back to the submitting chain
+                if let (Some(origin), true) = (origin, receipt_id > 0) {
+                    let receipt = match results.pop() {
+                        Some(RunResult { run_id: Some(run_id), xp_earned, .. }) => Message::RunAccepted {
+                            receipt_id,
+                            run_id,
+                            xp_earned,
+                            rank,
+                        },
+                        result => Message::RunRejected {
+                            receipt_id,
+                            reason: result.and_then(|r| r.reason).unwrap_or_default(),
+                        },
+                    };
+                    self.runtime.send_message(origin, receipt);
+                }
+            }
+
+            Message::ApplyRuns { wallet_address, username, tournament_id, runs, first_receipt_id } => {
+                let (results, rank) = if runs.len() > MAX_BATCH_RUNS {
+                    let reason = format!("At most {} runs per batch", MAX_BATCH_RUNS);
+                    (vec![RunResult::rejected(reason); runs.len()], None)
+                } else {
+                    self.apply_runs(wallet_address, username, tournament_id, runs).await
+                };
+                if let (Some(origin), true) = (origin, first_receipt_id > 0) {
+                    self.runtime.send_message(origin, Message::RunsApplied { first_receipt_id, results, rank });
+                }
+            }
+
+            Message::IssueTicket { wallet_address, tournament_id, nonce } => {
                 let now = self.runtime.system_time();
-                
-                // ===== STEP 1: Get tournament using .get() - returns a CLONE =====
-                // CRITICAL: Do NOT use get_mut() - it doesn't persist in RegisterView!
-                let mut tournament = match self.state.active_tournament.get() {
-                    Some(t) => t.clone(),
-                    None => return, // No active tournament
+                let accepting = match self.state.tournaments.get(&tournament_id).await.ok().flatten().map(Versioned::into_inner) {
+                    Some(t) => t.status == TournamentStatus::Active && t.seed_revealed && now < t.end_time,
+                    None => false,
                 };
-                
-                // Validate tournament
-                if tournament.id != tournament_id {
-                    return; // Wrong tournament ID
+                let key = (wallet_address, nonce);
+                if !accepting || self.state.run_tickets.contains_key(&key).await.unwrap_or(false) {
+                    return; // Closed tournament or nonce reuse
                 }
-                if tournament.status != TournamentStatus::Active {
-                    return; // Tournament not active
+                let Some(tournament) = self.state.tournaments.get(&tournament_id).await.ok().flatten().map(Versioned::into_inner) else {
+                    return;
+                };
+
+                let mut open = self.prune_expired_tickets(wallet_address, now).await;
+                if open.len() >= MAX_OPEN_RUN_TICKETS {
+                    return;
                 }
-                if now >= tournament.end_time {
-                    return; // Tournament ended
+
+                let id = std::cmp::max(*self.state.next_ticket_id.get(), 1);
+                self.state.next_ticket_id.set(id + 1);
+
+                // Drawn now, so the attempt's maze can't be known before the ticket exists
+                let entropy = self.seed_entropy(id);
+                let ticket = RunTicket {
+                    id,
+                    tournament_id,
+                    wallet_address,
+                    nonce,
+                    issued_at: now,
+                    entropy,
+                    attempt_seed: tournament.attempt_seed(&entropy),
+                };
+                let _ = self.state.run_tickets.insert(&key, ticket);
+                open.push(nonce);
+                let _ = self.state.open_run_tickets.insert(&wallet_address, open);
+            }
+
+            Message::CommitRun { wallet_address, tournament_id, commitment } => {
+                let accepting = match self.state.tournaments.get(&tournament_id).await.ok().flatten().map(Versioned::into_inner) {
+                    Some(t) => t.status == TournamentStatus::Active && t.seed_revealed,
+                    None => false,
+                };
+                if !accepting {
+                    return;
                 }
-                
-                // ===== STEP 2: Check if new participant =====
-                let key = (tournament_id, wallet_address);
-                let is_new_participant = !self.state.tournament_players
-                    .contains_key(&key)
-                    .await
-                    .unwrap_or(false);
-                
-                // ===== STEP 3: Mutate tournament counts (on the clone) =====
-                if is_new_participant {
-                    tournament.participant_count += 1;
-                }
-                tournament.total_runs += 1;
-                
-                // ===== STEP 4: Calculate XP =====
-                let xp_earned = tournament.difficulty.calculate_xp(time_ms, deaths, completed);
-                
-                // ===== STEP 5: Create run record =====
-                let run_id = *self.state.next_run_id.get();
-                self.state.next_run_id.set(run_id + 1);
-                
-                let run = GameRun {
-                    id: run_id,
+
+                let record = RunCommitment {
                     tournament_id,
                     wallet_address,
-                    username: username.clone(),
-                    time_ms,
-                    score,
-                    coins,
-                    deaths,
-                    completed,
-                    xp_earned,
+                    commitment,
+                    committed_at: self.runtime.system_time(),
+                };
+                let _ = self.state.run_commitments.insert(&(tournament_id, wallet_address), record);
+            }
+
+            Message::CreateClan { wallet_address, username, name, tag } => {
+                let name = name.trim().to_string();
+                let tag = tag.trim().to_uppercase();
+                if name.is_empty() || !(MIN_CLAN_TAG_LEN..=MAX_CLAN_TAG_LEN).contains(&tag.chars().count()) {
+                    return;
+                }
+                if self.state.wallet_to_clan.contains_key(&wallet_address).await.unwrap_or(false) {
+                    return; // Already in a clan
+                }
+                let name_key = name.to_lowercase();
+                if self.state.clan_names.contains_key(&name_key).await.unwrap_or(false) {
+                    return; // Name taken
+                }
+
+                let now = self.runtime.system_time();
+                let id = std::cmp::max(*self.state.next_clan_id.get(), 1);
+                self.state.next_clan_id.set(id + 1);
+
+                let clan = Clan {
+                    id,
+                    name,
+                    tag,
+                    leader: wallet_address,
+                    member_count: 1,
+                    total_xp: 0,
                     created_at: now,
                 };
-                
-                // Store run in MapView
-                let _ = self.state.runs.insert(&run_id, run);
-                
-                // ===== STEP 6: Update recent runs (RegisterView with .get().clone() / .set()) =====
-                let mut recent = self.state.recent_runs.get().clone();
-                recent.insert(0, run_id);
-                if recent.len() > 100 {
-                    recent.truncate(100);
-                }
-                self.state.recent_runs.set(recent);
-                
-                // ===== STEP 7: Get or create tournament player =====
-                let mut tp = match self.state.tournament_players.get(&key).await.ok().flatten() {
-                    Some(existing) => existing,
-                    None => TournamentPlayer {
-                        wallet_address,
-                        username: username.clone(),
-                        best_time_ms: u64::MAX,
-                        best_score: 0,
-                        total_runs: 0,
-                        total_xp_earned: 0,
-                        last_run_at: now,
-                        joined_at: now,
-                    },
+                let leader = ClanMember {
+                    clan_id: id,
+                    wallet_address,
+                    username,
+                    role: ClanRole::Leader,
+                    xp_contributed: 0,
+                    joined_at: now,
                 };
-                
-                // Update tournament player stats
-                if completed && time_ms < tp.best_time_ms {
-                    tp.best_time_ms = time_ms;
-                }
-                if score > tp.best_score {
-                    tp.best_score = score;
-                }
-                tp.total_runs += 1;
-                tp.total_xp_earned += xp_earned;
-                tp.last_run_at = now;
-                
-                // Store tournament player in MapView
-                let _ = self.state.tournament_players.insert(&key, tp.clone());
-                
-                // ===== STEP 8: Update global player stats =====
-                let mut player = self.state.players.get(&wallet_address).await.ok().flatten()
-                    .unwrap_or_else(|| Player {
-                        wallet_address,
-                        username: username.clone(),
-                        total_xp: 0,
-                        total_runs: 0,
-                        tournaments_played: 0,
-                        tournaments_won: 0,
-                        best_time_ms: None,
-                        registered_at: now,
-                        last_active: now,
-                    });
-                
-                player.total_xp += xp_earned;
-                player.total_runs += 1;
-                player.last_active = now;
-                if is_new_participant {
-                    player.tournaments_played += 1;
-                }
-                if completed {
-                    match player.best_time_ms {
-                        Some(best) if time_ms < best => player.best_time_ms = Some(time_ms),
-                        None => player.best_time_ms = Some(time_ms),
-                        _ => {}
-                    }
+
+                let _ = self.state.clans.insert(&id, clan);
+                let _ = self.state.clan_names.insert(&name_key, id);
+                let _ = self.state.clan_members.insert(&id, vec![leader]);
+                let _ = self.state.wallet_to_clan.insert(&wallet_address, id);
+            }
+
+            Message::InviteToClan { wallet_address, invitee } => {
+                let clan_id = match self.state.wallet_to_clan.get(&wallet_address).await.ok().flatten() {
+                    Some(id) => id,
+                    None => return,
+                };
+                let members = self.state.clan_members.get(&clan_id).await.ok().flatten()
+                    .unwrap_or_default();
+                let can_invite = members.iter().any(|m| {
+                    m.wallet_address == wallet_address && m.role != ClanRole::Member
+                });
+                if !can_invite || self.state.wallet_to_clan.contains_key(&invitee).await.unwrap_or(false) {
+                    return;
                 }
-                
-                // Store player in MapView
-                let _ = self.state.players.insert(&wallet_address, player);
-                
-                // ===== STEP 9: Update leaderboard (INLINE - no helper function) =====
-                let mut leaderboard = self.state.leaderboards
-                    .get(&tournament_id)
-                    .await
-                    .ok()
-                    .flatten()
+
+                let mut invites = self.state.clan_invites.get(&invitee).await.ok().flatten()
                     .unwrap_or_default();
-                
-                // Find or update entry
-                let mut found = false;
-                for entry in &mut leaderboard {
-                    if entry.wallet_address == wallet_address {
-                        if tp.best_time_ms < entry.best_time_ms {
-                            entry.best_time_ms = tp.best_time_ms;
-                        }
-                        if tp.best_score > entry.best_score {
-                            entry.best_score = tp.best_score;
-                        }
-                        entry.total_runs = tp.total_runs;
-                        entry.total_xp = tp.total_xp_earned;
-                        found = true;
-                        break;
-                    }
+                if !invites.contains(&clan_id) {
+                    invites.push(clan_id);
+                    let _ = self.state.clan_invites.insert(&invitee, invites);
                 }
-                
-                if !found {
-                    leaderboard.push(LeaderboardEntry {
-                        wallet_address,
-                        username: username.clone(),
-                        best_time_ms: tp.best_time_ms,
-                        best_score: tp.best_score,
-                        total_runs: tp.total_runs,
-                        total_xp: tp.total_xp_earned,
-                        rank: 0,
-                    });
-                }
-                
-                // Sort by best time (ascending - lower is better)
-                leaderboard.sort_by(|a, b| a.best_time_ms.cmp(&b.best_time_ms));
-                
-                // Update ranks
-                for (i, entry) in leaderboard.iter_mut().enumerate() {
-                    entry.rank = (i + 1) as u32;
-                }
-                
-                // Keep top 100
-                if leaderboard.len() > 100 {
-                    leaderboard.truncate(100);
-                }
-                
-                // Store leaderboard in MapView
-                let _ = self.state.leaderboards.insert(&tournament_id, leaderboard);
-                
-                // =====================================================================
-                // STEP 10: CRITICAL - Persist tournament state using .set()
-                // This is THE KEY FIX - get_mut() does NOT persist in RegisterView!
-                // We must use .set(Some(value)) to trigger state persistence.
-                // =====================================================================
-                self.state.active_tournament.set(Some(tournament.clone()));
-                
-                // Also sync to MapView for consistency
-                let _ = self.state.tournaments.insert(&tournament_id, tournament);
             }
-        }
-    }
 
-    async fn store(mut self) {
-        self.state.save().await.expect("Failed to save state");
-    }
-}
+            Message::JoinClan { wallet_address, username, clan_id } => {
+                let mut invites = self.state.clan_invites.get(&wallet_address).await.ok().flatten()
+                    .unwrap_or_default();
+                if !invites.contains(&clan_id) {
+                    return; // Not invited
+                }
+                if self.state.wallet_to_clan.contains_key(&wallet_address).await.unwrap_or(false) {
+                    return; // Already in a clan
+                }
+                let mut clan = match self.state.clans.get(&clan_id).await.ok().flatten() {
+                    Some(c) => c,
+                    None => return,
+                };
+                if clan.member_count >= MAX_CLAN_MEMBERS {
+                    return;
+                }
 
-impl LabyrinthTournamentContract {
-    // ===== Helper: Get wallet for signer =====
-    async fn get_wallet_for_signer(&self, signer: &AccountOwner) -> Option<[u8; 20]> {
-        self.state.signer_to_wallet.get(signer).await.ok().flatten()
-    }
+                let mut members = self.state.clan_members.get(&clan_id).await.ok().flatten()
+                    .unwrap_or_default();
+                members.push(ClanMember {
+                    clan_id,
+                    wallet_address,
+                    username,
+                    role: ClanRole::Member,
+                    xp_contributed: 0,
+                    joined_at: self.runtime.system_time(),
+                });
+                clan.member_count = members.len() as u32;
 
-    // ===== Helper: Get or create player =====
-    async fn get_or_create_player(&mut self, signer: AccountOwner, wallet: [u8; 20], default_username: &str) -> Player {
-        // Check if player exists
-        if let Some(player) = self.state.players.get(&wallet).await.ok().flatten() {
-            // Bind signer to wallet if not already
-            self.state.signer_to_wallet.insert(&signer, wallet).unwrap();
-            return player;
-        }
+                invites.retain(|id| *id != clan_id);
+                let _ = self.state.clan_invites.insert(&wallet_address, invites);
+                let _ = self.state.clan_members.insert(&clan_id, members);
+                let _ = self.state.clans.insert(&clan_id, clan);
+                let _ = self.state.wallet_to_clan.insert(&wallet_address, clan_id);
+                self.refresh_team_boards(clan_id).await;
+            }
 
-        // Create new player
-        let now = self.runtime.system_time();
-        let username = format!("Player_{}", hex::encode(&wallet[0..4]));
-        
-        let player = Player {
-            wallet_address: wallet,
-            username: username.clone(),
-            total_xp: 0,
-            total_runs: 0,
-            tournaments_played: 0,
-            tournaments_won: 0,
-            best_time_ms: None,
-            registered_at: now,
-            last_active: now,
-        };
+            Message::LeaveClan { wallet_address } => {
+                let clan_id = match self.state.wallet_to_clan.get(&wallet_address).await.ok().flatten() {
+                    Some(id) => id,
+                    None => return,
+                };
+                let mut clan = match self.state.clans.get(&clan_id).await.ok().flatten() {
+                    Some(c) => c,
+                    None => return,
+                };
+                let mut members = self.state.clan_members.get(&clan_id).await.ok().flatten()
+                    .unwrap_or_default();
+                members.retain(|m| m.wallet_address != wallet_address);
+                let _ = self.state.wallet_to_clan.remove(&wallet_address);
 
-        // Store player
-        self.state.players.insert(&wallet, player.clone()).unwrap();
-        self.state.username_to_wallet.insert(&username, wallet).unwrap();
-        self.state.signer_to_wallet.insert(&signer, wallet).unwrap();
+                // Last member out disbands the clan
+                if members.is_empty() {
+                    let _ = self.state.clans.remove(&clan_id);
+                    let _ = self.state.clan_members.remove(&clan_id);
+                    let _ = self.state.clan_names.remove(&clan.name.to_lowercase());
+                    self.refresh_team_boards(clan_id).await;
+                    return;
+                }
 
-        player
-    }
+                // Leader leaving: promote the longest-serving officer, else member
+                if clan.leader == wallet_address {
+                    let successor = members.iter()
+                        .position(|m| m.role == ClanRole::Officer)
+                        .unwrap_or(0);
+                    members[successor].role = ClanRole::Leader;
+                    clan.leader = members[successor].wallet_address;
+                }
 
-    // ===== Register Player =====
-    async fn register_player(
-        &mut self,
-        signer: AccountOwner,
-        wallet_address: [u8; 20],
-        username: String,
-    ) -> Response {
-        // Check if wallet already registered
-        if self.state.players.contains_key(&wallet_address).await.unwrap_or(false) {
-            // Just bind signer
-            self.state.signer_to_wallet.insert(&signer, wallet_address).unwrap();
-            return Response::PlayerRegistered { wallet_address };
-        }
+                clan.member_count = members.len() as u32;
+                let _ = self.state.clan_members.insert(&clan_id, members);
+                let _ = self.state.clans.insert(&clan_id, clan);
+                self.refresh_team_boards(clan_id).await;
+            }
 
-        // Check username uniqueness
-        if self.state.username_to_wallet.contains_key(&username).await.unwrap_or(false) {
-            return Response::Error { message: "Username already taken".to_string() };
-        }
+            Message::SetClanRole { wallet_address, member, role } => {
+                let clan_id = match self.state.wallet_to_clan.get(&wallet_address).await.ok().flatten() {
+                    Some(id) => id,
+                    None => return,
+                };
+                let mut clan = match self.state.clans.get(&clan_id).await.ok().flatten() {
+                    Some(c) => c,
+                    None => return,
+                };
+                if clan.leader != wallet_address || member == wallet_address {
+                    return; // Leader only, and not on themselves
+                }
+
+                let mut members = self.state.clan_members.get(&clan_id).await.ok().flatten()
+                    .unwrap_or_default();
+                let target = match members.iter().position(|m| m.wallet_address == member) {
+                    Some(i) => i,
+                    None => return,
+                };
+
+                // Handing over leadership demotes the old leader to officer
+                if role == ClanRole::Leader {
+                    for m in members.iter_mut().filter(|m| m.wallet_address == wallet_address) {
+                        m.role = ClanRole::Officer;
+                    }
+                    clan.leader = member;
+                    let _ = self.state.clans.insert(&clan_id, clan);
+                }
+                members[target].role = role;
+                let _ = self.state.clan_members.insert(&clan_id, members);
+            }
+
+            Message::OpenDuel { challenger, username, opponent, difficulty, duration_hours } => {
+                if opponent == Some(challenger) {
+                    return; // Can't duel yourself
+                }
+
+                let now = self.runtime.system_time();
+                let hours = duration_hours.clamp(MIN_DUEL_HOURS, MAX_DUEL_HOURS);
+                let deadline = linera_sdk::linera_base_types::Timestamp::from(
+                    now.micros() + hours * 60 * 60 * 1_000_000
+                );
+
+                let id = std::cmp::max(*self.state.next_duel_id.get(), 1);
+                self.state.next_duel_id.set(id + 1);
+
+                // Fresh seed per duel - neither player has seen this maze
+                // The "duel" secret keeps duel seeds apart from tournament seeds with the same id
+                let maze_seed = derive_maze_seed(&self.seed_entropy(id), DUEL_SEED_DOMAIN);
+
+                let duel = Duel {
+                    id,
+                    challenger,
+                    challenger_username: username,
+                    opponent,
+                    opponent_username: None,
+                    maze_seed,
+                    difficulty,
+                    status: DuelStatus::Open,
+                    challenger_run: None,
+                    opponent_run: None,
+                    winner: None,
+                    created_at: now,
+                    deadline,
+                };
+                let _ = self.state.duels.insert(&id, duel);
+
+                self.index_duel(challenger, id).await;
+                match opponent {
+                    Some(opponent) => self.index_duel(opponent, id).await,
+                    None => {
+                        let mut open = self.state.open_duels.get().clone();
+                        open.push(id);
+                        self.state.open_duels.set(open);
+                    }
+                }
+            }
+
+            Message::AcceptDuel { wallet_address, username, duel_id } => {
+                let now = self.runtime.system_time();
+                let mut duel = match self.state.duels.get(&duel_id).await.ok().flatten() {
+                    Some(d) => d,
+                    None => return,
+                };
+
+                if duel.status != DuelStatus::Open || now >= duel.deadline {
+                    return;
+                }
+                if wallet_address == duel.challenger {
+                    return; // Can't accept your own duel
+                }
+                if duel.opponent.is_some_and(|o| o != wallet_address) {
+                    return; // Directed at someone else
+                }
+
+                let was_open_lobby = duel.opponent.is_none();
+                duel.opponent = Some(wallet_address);
+                duel.opponent_username = Some(username);
+                duel.status = DuelStatus::Accepted;
+                let _ = self.state.duels.insert(&duel_id, duel);
+
+                if was_open_lobby {
+                    let mut open = self.state.open_duels.get().clone();
+                    open.retain(|id| *id != duel_id);
+                    self.state.open_duels.set(open);
+                    self.index_duel(wallet_address, duel_id).await;
+                }
+            }
+
+            // Handled before the hub check
+            Message::SetHub { .. }
+            | Message::RunAccepted { .. }
+            | Message::RunRejected { .. }
+            | Message::RunsApplied { .. }
+            | Message::LeaderboardDelta { .. }
+            | Message::PlayerSnapshot { .. }
+            | Message::HostTournament { .. }
+            | Message::TournamentLocation { .. } => {}
+
+            Message::LocateTournament { tournament_id } => {
+                if let Some(origin) = origin {
+                    let chain_id = self.tournament_chain(tournament_id).await;
+                    self.runtime.send_message(origin, Message::TournamentLocation { tournament_id, chain_id });
+                }
+            }
+
+            Message::ShardTournamentUpdate { tournament } => {
+                // Origin checked above. Only the Ended flip comes from the shard: the hub's copy
+                // keeps its own definition and settles from its mirrored board, which already
+                // holds every run the shard reported before finalizing
+                let id = tournament.id;
+                let mut stored = match self.state.tournaments.get(&id).await.ok().flatten().map(Versioned::into_inner) {
+                    Some(t) if t.status == TournamentStatus::Active && tournament.status == TournamentStatus::Ended => t,
+                    _ => return, // Cancelled on the hub meanwhile, or already settled
+                };
+                stored.status = TournamentStatus::Ended;
+                stored.participant_count = tournament.participant_count;
+                stored.total_runs = tournament.total_runs;
+                let _ = self.state.tournaments.insert(&id, Versioned::new(stored.clone()));
+                self.settle_tournament(&stored).await;
+            }
+
+            Message::ShardRunsApplied {
+                wallet_address,
+                username,
+                tournament_id,
+                participant_count,
+                total_runs,
+                new_participant,
+                tournament_player,
+                runs,
+                changes,
+                removed,
+            } => {
+                // Origin checked above. Runs the shard took after the hub cancelled earn nothing
+                let mut tournament = match self.state.tournaments.get(&tournament_id).await.ok().flatten().map(Versioned::into_inner) {
+                    Some(t) if t.status == TournamentStatus::Active => t,
+                    _ => return,
+                };
+                tournament.participant_count = participant_count;
+                tournament.total_runs = total_runs;
+                let _ = self.state.tournaments.insert(&tournament_id, Versioned::new(tournament.clone()));
+
+                // The runs themselves stay on the shard (run IDs are per chain)
+                let key = (tournament_id, wallet_address);
+                let _ = self.state.tournament_players.insert(&key, Versioned::new(*tournament_player));
+                if new_participant {
+                    let mut participants = self.state.tournament_participants.get(&tournament_id).await.ok().flatten()
+                        .unwrap_or_default();
+                    participants.push(wallet_address);
+                    let _ = self.state.tournament_participants.insert(&tournament_id, participants);
+                }
+                let mut board = self.state.leaderboards.get(&tournament_id).await.ok().flatten().unwrap_or_default();
+                apply_leaderboard_changes(&mut board, changes, &removed);
+                self.save_leaderboard(tournament_id, board).await;
+
+                self.credit_runs(&tournament, wallet_address, &username, new_participant, &runs).await;
+            }
+
+            Message::FinalizeTournament { .. } => {
+                // Lazy finalization above already ran it (and reported back to the hub)
+            }
+
+            Message::SubscribeSnapshots { wallet_address, tournament_id } => {
+                let Some(origin) = origin else { return };
+                let mut subscribers = self.state.snapshot_subscribers.get(&wallet_address).await.ok().flatten()
+                    .unwrap_or_default();
+                if !subscribers.contains(&origin) {
+                    subscribers.push(origin);
+                    let _ = self.state.snapshot_subscribers.insert(&wallet_address, subscribers);
+                }
+                let tournament_id = tournament_id.or(*self.state.active_tournament_id.get());
+                self.push_snapshot(wallet_address, tournament_id).await;
+            }
+
+            Message::UnsubscribeSnapshots { wallet_address } => {
+                let Some(origin) = origin else { return };
+                let mut subscribers = self.state.snapshot_subscribers.get(&wallet_address).await.ok().flatten()
+                    .unwrap_or_default();
+                subscribers.retain(|chain_id| *chain_id != origin);
+                let _ = if subscribers.is_empty() {
+                    self.state.snapshot_subscribers.remove(&wallet_address)
+                } else {
+                    self.state.snapshot_subscribers.insert(&wallet_address, subscribers)
+                };
+            }
+
+            Message::SubscribeLeaderboard { tournament_id } => {
+                let Some(origin) = origin else { return };
+                if origin == self.runtime.chain_id() {
+                    return; // This chain already holds the board
+                }
+                // Only running tournaments take subscribers (up to the cap);
+                // anyone else still gets the current board once
+                let active = self.state.tournaments.get(&tournament_id).await.ok().flatten().map(Versioned::into_inner)
+                    .is_some_and(|t| t.status == TournamentStatus::Active);
+                let mut subscribers = self.state.leaderboard_subscribers.get(&tournament_id).await.ok().flatten()
+                    .unwrap_or_default();
+                if active && !subscribers.contains(&origin) && subscribers.len() < MAX_LEADERBOARD_SUBSCRIBERS {
+                    subscribers.push(origin);
+                    let _ = self.state.leaderboard_subscribers.insert(&tournament_id, subscribers);
+                }
+                // Start (or resync) the subscriber with the whole board
+                let board = self.state.leaderboards.get(&tournament_id).await.ok().flatten().unwrap_or_default();
+                let (changes, _) = leaderboard_changes(&[], &board);
+                self.runtime.send_message(origin, Message::LeaderboardDelta {
+                    tournament_id,
+                    changes,
+                    removed: Vec::new(),
+                    reset: true,
+                });
+            }
+
+            Message::UnsubscribeLeaderboard { tournament_id } => {
+                let Some(origin) = origin else { return };
+                let mut subscribers = self.state.leaderboard_subscribers.get(&tournament_id).await.ok().flatten()
+                    .unwrap_or_default();
+                subscribers.retain(|chain_id| *chain_id != origin);
+                let _ = if subscribers.is_empty() {
+                    self.state.leaderboard_subscribers.remove(&tournament_id)
+                } else {
+                    self.state.leaderboard_subscribers.insert(&tournament_id, subscribers)
+                };
+            }
+
+            Message::ApplyDuelRun { wallet_address, run: submission } => {
+                let DuelRunSubmission { duel_id, time_ms, score, coins, deaths, completed } = submission;
+                let now = self.runtime.system_time();
+                let mut duel = match self.state.duels.get(&duel_id).await.ok().flatten() {
+                    Some(d) => d,
+                    None => return,
+                };
+
+                if now >= duel.deadline {
+                    return; // Too late - ResolveDuel settles it
+                }
+
+                let run = DuelRun {
+                    time_ms,
+                    score,
+                    coins,
+                    deaths,
+                    completed,
+                    xp_earned: duel.difficulty.calculate_xp(time_ms, deaths, completed),
+                    submitted_at: now,
+                };
+
+                // One run per side; the opponent can only play once accepted
+                if wallet_address == duel.challenger
+                    && matches!(duel.status, DuelStatus::Open | DuelStatus::Accepted)
+                    && duel.challenger_run.is_none()
+                {
+                    duel.challenger_run = Some(run.clone());
+                } else if duel.opponent == Some(wallet_address)
+                    && duel.status == DuelStatus::Accepted
+                    && duel.opponent_run.is_none()
+                {
+                    duel.opponent_run = Some(run.clone());
+                } else {
+                    return;
+                }
+
+                // Duel runs count towards global player stats
+                if let Some(mut player) = self.state.players.get(&wallet_address).await.ok().flatten().map(Versioned::into_inner) {
+                    player.total_xp += run.xp_earned;
+                    player.total_runs += 1;
+                    player.last_active = now;
+                    if completed && player.best_time_ms.is_none_or(|best| time_ms < best) {
+                        player.best_time_ms = Some(time_ms);
+                    }
+                    let _ = self.state.players.insert(&wallet_address, Versioned::new(player));
+                }
+
+                if duel.challenger_run.is_some() && duel.opponent_run.is_some() {
+                    self.settle_duel(&mut duel).await;
+                    let opponent = if wallet_address == duel.challenger { duel.opponent } else { Some(duel.challenger) };
+                    if let Some(opponent) = opponent {
+                        self.push_snapshot(opponent, None).await;
+                    }
+                }
+                let _ = self.state.duels.insert(&duel_id, duel);
+                self.push_snapshot(wallet_address, None).await;
+            }
+        }
+    }
+
+    async fn store(mut self) {
+        self.state.save().await.expect("Failed to save state");
+    }
+}
+
+impl LabyrinthTournamentContract {
+    // ===== Helper: Get wallet for signer =====
+    async fn get_wallet_for_signer(&self, signer: &AccountOwner) -> Option<[u8; 20]> {
+        self.state.signer_to_wallet.get(signer).await.ok().flatten()
+    }
+
+    // ===== Helper: Get or create player =====
+    async fn get_or_create_player(&mut self, signer: AccountOwner, wallet: [u8; 20], default_username: &str) -> Player {
+        // Check if player exists
+        if let Some(player) = self.state.players.get(&wallet).await.ok().flatten().map(Versioned::into_inner) {
+            // Bind signer to wallet if not already
+            self.state.signer_to_wallet.insert(&signer, wallet).unwrap();
+            return player;
+        }
+
+        // Create new player
+        let now = self.runtime.system_time();
+        let username = format!("Player_{}", hex::encode(&wallet[0..4]));
+        
+        let player = Player {
+            wallet_address: wallet,
+            username: username.clone(),
+            total_xp: 0,
+            total_runs: 0,
+            tournaments_played: 0,
+            tournaments_won: 0,
+            best_time_ms: None,
+            registered_at: now,
+            last_active: now,
+            rating: DEFAULT_RATING,
+            rating_deviation: DEFAULT_RATING_DEVIATION,
+            rated_tournaments: 0,
+            rating_provisional: true,
+            duels_played: 0,
+            duels_won: 0,
+        };
+
+        // Store player
+        self.state.players.insert(&wallet, Versioned::new(player.clone())).unwrap();
+        self.state.username_to_wallet.insert(&username, wallet).unwrap();
+        self.state.signer_to_wallet.insert(&signer, wallet).unwrap();
+
+        player
+    }
+
+    // ===== Helper: Unlock achievement (no-op if already unlocked) =====
+    async fn unlock_achievement(
+        &mut self,
+        wallet: [u8; 20],
+        achievement: AchievementId,
+        tournament_id: u64,
+        run_id: Option<u64>,
+    ) {
+        let key = (wallet, achievement);
+        if self.state.achievements.contains_key(&key).await.unwrap_or(false) {
+            return;
+        }
+
+        let unlocked = PlayerAchievement {
+            achievement,
+            wallet_address: wallet,
+            tournament_id,
+            run_id,
+            unlocked_at: self.runtime.system_time(),
+        };
+        let _ = self.state.achievements.insert(&key, unlocked);
+    }
+
+    // ===== Register Player =====
+    async fn register_player(
+        &mut self,
+        signer: AccountOwner,
+        wallet_address: [u8; 20],
+        username: String,
+    ) -> Response {
+        // Check if wallet already registered
+        if self.state.players.contains_key(&wallet_address).await.unwrap_or(false) {
+            // Just bind signer
+            self.state.signer_to_wallet.insert(&signer, wallet_address).unwrap();
+            return Response::PlayerRegistered { wallet_address };
+        }
+
+        // Check username uniqueness
+        if self.state.username_to_wallet.contains_key(&username).await.unwrap_or(false) {
+            return Response::Error { message: "Username already taken".to_string() };
+        }
+
+        let now = self.runtime.system_time();
+
+        let player = Player {
+            wallet_address,
+            username: username.clone(),
+            total_xp: 0,
+            total_runs: 0,
+            tournaments_played: 0,
+            tournaments_won: 0,
+            best_time_ms: None,
+            registered_at: now,
+            last_active: now,
+            rating: DEFAULT_RATING,
+            rating_deviation: DEFAULT_RATING_DEVIATION,
+            rated_tournaments: 0,
+            rating_provisional: true,
+            duels_played: 0,
+            duels_won: 0,
+        };
+
+        // Store everything
+        self.state.players.insert(&wallet_address, Versioned::new(player)).unwrap();
+        self.state.username_to_wallet.insert(&username, wallet_address).unwrap();
+        self.state.signer_to_wallet.insert(&signer, wallet_address).unwrap();
+
+        Response::PlayerRegistered { wallet_address }
+    }
+
+    // ===== Submit Run (PRIMARY OPERATION) =====
+    // CRITICAL: This function ALWAYS sends a message to hub chain.
+    // NO branching logic. NO direct state mutation.
+    // All state changes happen ONLY in execute_message.
+    async fn submit_run(&mut self, signer: AccountOwner, run: RunSubmission) -> Response {
+        // Reject inconsistent splits and oversized payloads early (the hub re-checks)
+        if let Err(message) = run.precheck() {
+            return Response::Error { message };
+        }
+
+        // Get wallet for signer (auto-registers EVM wallets) and username
+        let (wallet, username) = match self.resolve_player(signer).await {
+            Ok(p) => p,
+            Err(response) => return response,
+        };
+
+        // Receipt the hub answers with RunAccepted / RunRejected
+        let receipt_id = self.open_receipt(wallet, &run);
+
+        // ALWAYS send message to hub chain - NO branching logic
+        // Even if we ARE on the hub chain, we send a message to ourselves
+        // This ensures ALL state mutations go through execute_message
+        let host = self.tournament_chain(run.tournament_id).await;
+        let message = Message::ApplyRun {
+            wallet_address: wallet,
+            username,
+            run,
+            receipt_id,
+        };
+        
+        // Tracked so a refused message bounces back, authenticated so the host sees the signer
+        self.runtime
+            .prepare_message(message)
+            .with_tracking()
+            .with_authentication()
+            .send_to(host);
+        
+        // Actual state update happens in execute_message; the receipt tracks the outcome
+        Response::RunQueued { receipt_id }
+    }
+
+    // ===== Submit Runs (batch) =====
+    // Same contract as submit_run: validate, open receipts, send one message to the host
+    async fn submit_runs(&mut self, signer: AccountOwner, runs: Vec<RunSubmission>) -> Response {
+        let Some(tournament_id) = runs.first().map(|r| r.tournament_id) else {
+            return Response::Error { message: "No runs to submit".to_string() };
+        };
+        if runs.len() > MAX_BATCH_RUNS {
+            return Response::Error { message: format!("At most {} runs per batch", MAX_BATCH_RUNS) };
+        }
+        if runs.iter().any(|r| r.tournament_id != tournament_id) {
+            return Response::Error { message: "All runs in a batch must be for the same tournament".to_string() };
+        }
+        for (index, run) in runs.iter().enumerate() {
+            if let Err(message) = run.precheck() {
+                return Response::Error { message: format!("Run {}: {}", index + 1, message) };
+            }
+        }
+
+        let (wallet, username) = match self.resolve_player(signer).await {
+            Ok(p) => p,
+            Err(response) => return response,
+        };
+
+        // One receipt per run; ids are consecutive so RunsApplied can answer them in order
+        let receipt_ids: Vec<u64> = runs.iter().map(|run| self.open_receipt(wallet, run)).collect();
+        let message = Message::ApplyRuns {
+            wallet_address: wallet,
+            username,
+            tournament_id,
+            runs,
+            first_receipt_id: receipt_ids[0],
+        };
+
+        let host = self.tournament_chain(tournament_id).await;
+        self.runtime
+            .prepare_message(message)
+            .with_tracking()
+            .with_authentication()
+            .send_to(host);
+
+        Response::RunsQueued { receipt_ids }
+    }
+
+    /// Pending receipt for a run about to be sent to its host
+    fn open_receipt(&mut self, wallet: [u8; 20], run: &RunSubmission) -> u64 {
+        let receipt_id = std::cmp::max(*self.state.next_receipt_id.get(), 1);
+        self.state.next_receipt_id.set(receipt_id + 1);
+        let receipt = RunReceipt {
+            id: receipt_id,
+            wallet_address: wallet,
+            tournament_id: run.tournament_id,
+            time_ms: run.time_ms,
+            ticket_nonce: run.ticket_nonce,
+            status: ReceiptStatus::Pending,
+            run_id: None,
+            xp_earned: 0,
+            rank: None,
+            reason: None,
+            submitted_at: self.runtime.system_time(),
+            resolved_at: None,
+        };
+        self.state.run_receipts.insert(&receipt_id, receipt).unwrap();
+        receipt_id
+    }
+
+    // ===== Start Run =====
+    // Sent to the hub so the ticket is timestamped by the hub chain
+    async fn start_run(
+        &mut self,
+        signer: AccountOwner,
+        tournament_id: u64,
+        nonce: u64,
+    ) -> Response {
+        let (wallet, _) = match self.resolve_player(signer).await {
+            Ok(p) => p,
+            Err(response) => return response,
+        };
+
+        let host = self.tournament_chain(tournament_id).await;
+        self.runtime.send_message(host, Message::IssueTicket {
+            wallet_address: wallet,
+            tournament_id,
+            nonce,
+        });
+        Response::Ok
+    }
+
+    // ===== Commit Run =====
+    // Sent to the hub so the commitment is timestamped by the hub chain
+    async fn commit_run(
+        &mut self,
+        signer: AccountOwner,
+        tournament_id: u64,
+        commitment: [u8; 32],
+    ) -> Response {
+        let (wallet, _) = match self.resolve_player(signer).await {
+            Ok(p) => p,
+            Err(response) => return response,
+        };
+
+        let host = self.tournament_chain(tournament_id).await;
+        self.runtime.send_message(host, Message::CommitRun {
+            wallet_address: wallet,
+            tournament_id,
+            commitment,
+        });
+        Response::Ok
+    }
+
+    // NOTE: apply_run_on_hub has been DELETED
+    // NOTE: update_leaderboard has been DELETED
+    // ALL state mutations now happen ONLY inside execute_message
+
+    // ===== Create Tournament =====
+    async fn create_tournament(
+        &mut self,
+        organizer: Option<AccountOwner>,
+        params: TournamentParams,
+    ) -> Response {
+        let TournamentParams {
+            title,
+            description,
+            seed_commitment,
+            difficulty,
+            duration_micros,
+            xp_reward_pool,
+            team_mode,
+            team_best_n,
+            commit_reveal_required,
+            seed_mode,
+            maze_config,
+            reward_percentages,
+            template_id,
+        } = params;
+        if team_mode == TeamMode::TeamBestN && team_best_n == 0 {
+            return Response::Error { message: "TeamBestN needs team_best_n > 0".to_string() };
+        }
+        if let Err(message) = maze_config.validate() {
+            return Response::Error { message };
+        }
+        if let Err(message) = validate_reward_curve(&reward_percentages) {
+            return Response::Error { message };
+        }
+
+        let now = self.runtime.system_time();
+        
+        let end_time = match now.micros().checked_add(duration_micros) {
+            Some(end) => linera_sdk::linera_base_types::Timestamp::from(end),
+            None => return Response::Error { message: "Tournament duration is too long".to_string() },
+        };
+
+        // 0 on chains whose instantiation didn't persist (see bootstrap_tournament)
+        let id = std::cmp::max(*self.state.next_tournament_id.get(), 1);
+        self.state.next_tournament_id.set(id + 1);
+
+        // With a commitment the seed stays hidden until the organizer reveals the secret
+        let seed_entropy = self.seed_entropy(id);
+        let (maze_seed, reference_path_length) = match seed_commitment {
+            Some(_) => (String::new(), 0),
+            None => {
+                let seed = derive_maze_seed(&seed_entropy, &[]);
+                let length = maze_path_length(&seed, &maze_config);
+                (seed, length)
+            }
+        };
+
+        // Attach to the active season (if any)
+        let mut season = match *self.state.active_season_id.get() {
+            Some(season_id) => self.state.seasons.get(&season_id).await.ok().flatten()
+                .filter(|s| s.status == SeasonStatus::Active),
+            None => None,
+        };
+        if let Some(season) = season.as_mut() {
+            season.tournament_ids.push(id);
+            self.state.seasons.insert(&season.id, season.clone()).unwrap();
+        }
+
+        let tournament = Tournament {
+            id,
+            title,
+            description,
+            maze_seed: maze_seed.clone(),
+            difficulty,
+            start_time: now,
+            end_time,
+            status: TournamentStatus::Active,
+            participant_count: 0,
+            total_runs: 0,
+            xp_reward_pool,
+            created_at: now,
+            season_id: season.map(|s| s.id),
+            team_mode,
+            team_best_n,
+            commit_reveal_required,
+            seed_mode,
+            reference_path_length,
+            seed_commitment,
+            seed_entropy,
+            seed_secret: None,
+            seed_revealed: seed_commitment.is_none(),
+            maze_config,
+            organizer,
+            cancelled_at: None,
+            reward_percentages,
+            template_id,
+        };
+
+        // Sharded hub: the next shard in turn runs it, the hub keeps the directory copy
+        let shards = self.state.shard_chains.get().clone();
+        if self.is_hub() && !shards.is_empty() {
+            let shard = shards[(id % shards.len() as u64) as usize];
+            self.state.tournament_shards.insert(&id, shard).unwrap();
+            self.runtime.send_message(shard, Message::HostTournament { tournament: Box::new(tournament.clone()) });
+        }
+
+        if tournament.team_mode != TeamMode::Individual {
+            let mut team_tournaments = self.state.team_tournaments.get().clone();
+            team_tournaments.push(id);
+            self.state.team_tournaments.set(team_tournaments);
+        }
+        self.state.tournaments.insert(&id, Versioned::new(tournament)).unwrap();
+        self.state.leaderboards.insert(&id, Vec::new()).unwrap();
+
+        // Organizer tournaments take the spotlight; template instances only fill it when
+        // the featured tournament is gone or over, so rotations don't keep replacing it
+        let featured_running = match *self.state.active_tournament_id.get() {
+            Some(featured) => self.state.tournaments.get(&featured).await.ok().flatten().map(Versioned::into_inner)
+                .is_some_and(|t| t.status == TournamentStatus::Active && now < t.end_time),
+            None => false,
+        };
+        if template_id.is_none() || !featured_running {
+            self.state.active_tournament_id.set(Some(id));
+        }
+
+        Response::TournamentCreated {
+            id,
+            maze_seed,
+            end_time,
+        }
+    }
+
+    // ===== Reveal Tournament Seed =====
+    // Anyone holding the committed secret can reveal it; the seed is then fixed
+    async fn reveal_tournament_seed(&mut self, tournament_id: u64, secret: Vec<u8>) -> Response {
+        let mut tournament = match self.state.tournaments.get(&tournament_id).await {
+            Ok(Some(t)) => t.into_inner(),
+            _ => return Response::Error { message: "Tournament not found".to_string() },
+        };
+        if tournament.seed_revealed {
+            return Response::Error { message: "Seed already revealed".to_string() };
+        }
+        if secret.len() > MAX_SEED_SECRET_BYTES {
+            return Response::Error { message: "Seed secret too large".to_string() };
+        }
+        if tournament.seed_commitment != Some(compute_seed_commitment(&secret)) {
+            return Response::Error { message: "Secret does not match seed commitment".to_string() };
+        }
+
+        tournament.maze_seed = derive_maze_seed(&tournament.seed_entropy, &secret);
+        tournament.reference_path_length = maze_path_length(&tournament.maze_seed, &tournament.maze_config);
+        tournament.seed_secret = Some(secret);
+        tournament.seed_revealed = true;
+
+        self.sync_shard(&tournament).await;
+        self.state.tournaments.insert(&tournament_id, Versioned::new(tournament.clone())).unwrap();
+
+        Response::TournamentSeedRevealed {
+            id: tournament_id,
+            maze_seed: tournament.maze_seed,
+        }
+    }
+
+    // ===== Create Template =====
+    // Hub chain only: templates are run by the ops team
+    async fn create_template(&mut self, input: TemplateInput) -> Response {
+        if !self.is_hub() {
+            return Response::Error { message: "Templates can only be created on the hub chain".to_string() };
+        }
+        match self.insert_template(input) {
+            Ok(id) => Response::TemplateCreated { id },
+            Err(message) => Response::Error { message },
+        }
+    }
+
+    /// Every deployment's first template; its instances are the featured championship
+    fn seed_default_template(&mut self) {
+        self.insert_template(TemplateInput::default_championship())
+            .expect("Default template is valid");
+    }
+
+    /// Validates and stores a template; its first instance spawns on the next rotation
+    fn insert_template(&mut self, input: TemplateInput) -> Result<u64, String> {
+        if input.title_pattern.trim().is_empty() {
+            return Err("Title pattern cannot be empty".to_string());
+        }
+        let period_hours = input.recurrence.period_micros() / (60 * 60 * 1_000_000);
+        if input.duration_hours == 0 || input.duration_hours > period_hours {
+            return Err(format!("Duration must be 1-{} hours for this recurrence", period_hours));
+        }
+        if input.team_mode == TeamMode::TeamBestN && input.team_best_n == 0 {
+            return Err("TeamBestN needs team_best_n > 0".to_string());
+        }
+        let maze_config = input.maze_config.unwrap_or_else(|| input.difficulty.default_maze_config());
+        maze_config.validate()?;
+        let reward_percentages = if input.reward_percentages.is_empty() {
+            DEFAULT_REWARD_PERCENTAGES.to_vec()
+        } else {
+            input.reward_percentages
+        };
+        validate_reward_curve(&reward_percentages)?;
+
+        let id = std::cmp::max(*self.state.next_template_id.get(), 1);
+        self.state.next_template_id.set(id + 1);
+
+        let now = self.runtime.system_time();
+        let template = TournamentTemplate {
+            id,
+            title_pattern: input.title_pattern,
+            description: input.description,
+            difficulty: input.difficulty,
+            maze_config,
+            duration_hours: input.duration_hours,
+            xp_reward_pool: input.xp_reward_pool,
+            reward_percentages,
+            recurrence: input.recurrence,
+            seed_mode: input.seed_mode,
+            team_mode: input.team_mode,
+            team_best_n: input.team_best_n,
+            active: true,
+            instances_created: 0,
+            current_tournament_id: None,
+            next_start: now, // First instance spawns on the next rotation
+            created_at: now,
+        };
+        self.state.templates.insert(&id, template).unwrap();
+
+        Ok(id)
+    }
+
+    // ===== Set Template Active =====
+    async fn set_template_active(&mut self, template_id: u64, active: bool) -> Response {
+        if !self.is_hub() {
+            return Response::Error { message: "Templates can only be changed on the hub chain".to_string() };
+        }
+        let mut template = match self.state.templates.get(&template_id).await.ok().flatten() {
+            Some(t) => t,
+            None => return Response::Error { message: "Template not found".to_string() },
+        };
+        template.active = active;
+        self.state.templates.insert(&template_id, template).unwrap();
+        Response::Ok
+    }
+
+    // ===== Rotate Tournaments =====
+    // Permissionless: finalizes expired template instances and spawns the next ones
+    async fn rotate_tournaments(&mut self) -> Response {
+        let now = self.runtime.system_time();
+        let mut templates = self.state.templates.index_values().await.unwrap_or_default();
+        templates.sort_by_key(|(id, _)| *id);
+
+        let mut ended = Vec::new();
+        let mut started = Vec::new();
+        for (template_id, mut template) in templates {
+            if !template.active {
+                continue;
+            }
+
+            // At most one running instance per template
+            if let Some(current) = template.current_tournament_id {
+                if self.finalize_if_expired(current).await {
+                    ended.push(current);
+                }
+                // Sharded instances are finalized on their shard; the hub's copy may lag
+                let running = self.state.tournaments.get(&current).await.ok().flatten().map(Versioned::into_inner)
+                    .is_some_and(|t| t.status == TournamentStatus::Active && now < t.end_time);
+                if running {
+                    continue;
+                }
+            }
+            if now < template.next_start {
+                continue;
+            }
+
+            let instance = template.instances_created + 1;
+            let response = self.create_tournament(None, template.instance_params(instance)).await;
+            let tournament_id = match response {
+                Response::TournamentCreated { id, .. } => id,
+                _ => continue,
+            };
+
+            template.instances_created = instance;
+            template.current_tournament_id = Some(tournament_id);
+            template.next_start = template.next_slot_after(now);
+            self.state.templates.insert(&template_id, template).unwrap();
+            started.push(tournament_id);
+        }
+
+        Response::TournamentsRotated { ended, started }
+    }
+
+    // ===== Update Tournament =====
+    // Organizer or hub chain owners only; fairness-relevant fields lock at the first run
+    async fn update_tournament(
+        &mut self,
+        caller: AccountOwner,
+        tournament_id: u64,
+        mut update: TournamentUpdate,
+    ) -> Response {
+        let mut tournament = match self.state.tournaments.get(&tournament_id).await.ok().flatten().map(Versioned::into_inner) {
+            Some(t) => t,
+            None => return Response::Error { message: "Tournament not found".to_string() },
+        };
+
+        if !self.can_administer(caller, &tournament) {
+            return Response::Error { message: "Only the organizer or the hub chain can amend".to_string() };
+        }
+        if tournament.status != TournamentStatus::Active {
+            return Response::Error { message: "Only active tournaments can be amended".to_string() };
+        }
+        let locks_at_first_run = update.title.is_some()
+            || update.description.is_some()
+            || update.difficulty.is_some()
+            || update.maze_config.is_some();
+        if locks_at_first_run && tournament.total_runs > 0 {
+            return Response::Error {
+                message: "Title, description, difficulty and maze are locked after the first run".to_string(),
+            };
+        }
+        if update.title.as_ref().is_some_and(|t| t.trim().is_empty()) {
+            return Response::Error { message: "Title cannot be empty".to_string() };
+        }
+        // A new difficulty without an explicit maze gets that difficulty's standard maze
+        if update.maze_config.is_none() {
+            update.maze_config = update.difficulty.map(|d| d.default_maze_config());
+        }
+        if let Some(Err(message)) = update.maze_config.as_ref().map(|c| c.validate()) {
+            return Response::Error { message };
+        }
+        if update.extend_hours == Some(0) || update.add_xp_reward_pool == Some(0) {
+            return Response::Error { message: "Extensions and pool increases must be positive".to_string() };
+        }
+
+        let now = self.runtime.system_time();
+        let new_end_time = match update.extend_hours {
+            Some(_) if now >= tournament.end_time => {
+                return Response::Error { message: "Tournament has already ended".to_string() };
+            }
+            Some(hours) => match hours.checked_mul(60 * 60 * 1_000_000)
+                .and_then(|extra| tournament.end_time.micros().checked_add(extra))
+            {
+                Some(micros) => Some(linera_sdk::linera_base_types::Timestamp::from(micros)),
+                None => return Response::Error { message: "Extension is too long".to_string() },
+            },
+            None => None,
+        };
+        let mut amendments = Vec::new();
+        let mut record = |field: AmendedField, old_value: String, new_value: String| {
+            amendments.push(TournamentAmendment {
+                tournament_id,
+                field,
+                old_value,
+                new_value,
+                amended_by: caller,
+                amended_at: now,
+            });
+        };
+
+        if let Some(title) = update.title {
+            let old = std::mem::replace(&mut tournament.title, title.clone());
+            record(AmendedField::Title, old, title);
+        }
+        if let Some(description) = update.description {
+            let old = std::mem::replace(&mut tournament.description, description.clone());
+            record(AmendedField::Description, old, description);
+        }
+        if let Some(difficulty) = update.difficulty {
+            record(AmendedField::Difficulty, format!("{:?}", tournament.difficulty), format!("{:?}", difficulty));
+            tournament.difficulty = difficulty;
+        }
+        if let Some(maze_config) = update.maze_config {
+            record(AmendedField::MazeConfig, format!("{:?}", tournament.maze_config), format!("{:?}", maze_config));
+            tournament.maze_config = maze_config;
+            if tournament.seed_revealed {
+                tournament.reference_path_length = maze_path_length(&tournament.maze_seed, &tournament.maze_config);
+            }
+        }
+        if let Some(end_time) = new_end_time {
+            record(AmendedField::EndTime, tournament.end_time.micros().to_string(), end_time.micros().to_string());
+            tournament.end_time = end_time;
+        }
+        if let Some(extra) = update.add_xp_reward_pool {
+            let pool = tournament.xp_reward_pool.saturating_add(extra);
+            record(AmendedField::XpRewardPool, tournament.xp_reward_pool.to_string(), pool.to_string());
+            tournament.xp_reward_pool = pool;
+        }
+
+        if amendments.is_empty() {
+            return Response::Error { message: "Nothing to update".to_string() };
+        }
+
+        self.sync_shard(&tournament).await;
+        self.state.tournaments.insert(&tournament_id, Versioned::new(tournament)).unwrap();
+
+        let count = amendments.len() as u32;
+        let mut log = self.state.tournament_amendments.get(&tournament_id).await.ok().flatten()
+            .unwrap_or_default();
+        log.extend(amendments);
+        self.state.tournament_amendments.insert(&tournament_id, log).unwrap();
+
+        Response::TournamentUpdated {
+            id: tournament_id,
+            amendments: count,
+        }
+    }
+
+    // ===== Cancel Tournament =====
+    // Organizer or hub chain owners only; skips rewards, ratings and season points
+    async fn cancel_tournament(
+        &mut self,
+        caller: AccountOwner,
+        tournament_id: u64,
+        xp_policy: CancelXpPolicy,
+    ) -> Response {
+        let mut tournament = match self.state.tournaments.get(&tournament_id).await.ok().flatten().map(Versioned::into_inner) {
+            Some(t) => t,
+            None => return Response::Error { message: "Tournament not found".to_string() },
+        };
+
+        if !self.can_administer(caller, &tournament) {
+            return Response::Error { message: "Only the organizer or the hub chain can cancel".to_string() };
+        }
+        if tournament.status != TournamentStatus::Active {
+            return Response::Error { message: "Only active tournaments can be cancelled".to_string() };
+        }
+
+        tournament.status = TournamentStatus::Cancelled;
+        tournament.cancelled_at = Some(self.runtime.system_time());
+        self.sync_shard(&tournament).await;
+        self.state.tournaments.insert(&tournament_id, Versioned::new(tournament)).unwrap();
+
+        // Remove from the active set (ApplyRun already rejects non-Active tournaments)
+        if self.state.active_tournament_id.get() == &Some(tournament_id) {
+            self.state.active_tournament_id.set(None);
+        }
+
+        // Achievements unlocked by the tournament's runs are kept (see CancelXpPolicy).
+        // XP is reverted from the hub's copies; a shard's own stats are not used again
+        let mut xp_reverted = 0u64;
+        if xp_policy == CancelXpPolicy::Revert {
+            let participants = self.state.tournament_participants.get(&tournament_id).await.ok().flatten()
+                .unwrap_or_default();
+            for wallet in participants {
+                let key = (tournament_id, wallet);
+                let mut tp = match self.state.tournament_players.get(&key).await.ok().flatten().map(Versioned::into_inner) {
+                    Some(tp) if tp.total_xp_earned > 0 => tp,
+                    _ => continue,
+                };
+                if let Some(mut player) = self.state.players.get(&wallet).await.ok().flatten().map(Versioned::into_inner) {
+                    player.total_xp = player.total_xp.saturating_sub(tp.total_xp_earned);
+                    self.state.players.insert(&wallet, Versioned::new(player)).unwrap();
+                }
+                let credits = self.state.tournament_clan_xp.get(&key).await.ok().flatten()
+                    .unwrap_or_default();
+                for credit in credits {
+                    self.debit_clan_xp(credit.clan_id, wallet, credit.xp).await;
+                }
+                self.state.tournament_clan_xp.remove(&key).unwrap();
+                xp_reverted += tp.total_xp_earned;
+                tp.total_xp_earned = 0;
+                self.state.tournament_players.insert(&key, Versioned::new(tp)).unwrap();
+            }
+
+            if let Some(mut leaderboard) = self.state.leaderboards.get(&tournament_id).await.ok().flatten() {
+                for entry in &mut leaderboard {
+                    entry.total_xp = 0;
+                }
+                self.save_leaderboard(tournament_id, leaderboard).await;
+            }
+        }
+
+        // Revert pushed its last delta above; the board is frozen now
+        self.state.leaderboard_subscribers.remove(&tournament_id).unwrap();
+
+        Response::TournamentCancelled {
+            id: tournament_id,
+            xp_reverted,
+        }
+    }
+
+    // ===== End Tournament (Finalize) =====
+    // STRICT ENFORCEMENT: Can only be called when now >= end_time
+    // This ensures the tournament runs for the full duration
+    // Anyone can call this (not admin-only) - the contract enforces timing
+    async fn end_tournament(
+        &mut self,
+        _caller: AccountOwner,
+        tournament_id: u64,
+    ) -> Response {
+        let tournament = match self.state.tournaments.get(&tournament_id).await.ok().flatten().map(Versioned::into_inner) {
+            Some(t) => t,
+            None => return Response::Error { message: "Tournament not found".to_string() },
+        };
+
+        match tournament.status {
+            TournamentStatus::Active => {}
+            // Already finalized (possibly lazily) - report the same outcome again
+            TournamentStatus::Ended => {
+                let leaderboard = self.state.leaderboards.get(&tournament_id).await.ok().flatten()
+                    .unwrap_or_default();
+                return Response::TournamentEnded {
+                    id: tournament_id,
+                    winner_count: leaderboard.len().min(tournament.reward_percentages.len()) as u32,
+                };
+            }
+            TournamentStatus::Cancelled => {
+                return Response::Error { message: "Tournament was cancelled".to_string() };
+            }
+        }
+
+        // STRICT TIME ENFORCEMENT: Tournament must have reached end_time
+        let now = self.runtime.system_time();
+        if now < tournament.end_time {
+            return Response::Error { 
+                message: format!(
+                    "Tournament cannot be finalized yet. Ends at timestamp {}",
+                    tournament.end_time.micros()
+                )
+            };
+        }
+
+        // A shard finalizes its own board and reports back; the hub settles then
+        if self.forward_finalize(tournament_id).await {
+            return Response::Ok;
+        }
+        let winner_count = self.finalize_tournament(tournament).await;
+
+        Response::TournamentEnded {
+            id: tournament_id,
+            winner_count,
+        }
+    }
+
+    // ===== Reconcile Tournaments (Migration) =====
+    // Older deployments mirrored the active tournament into a RegisterView and the
+    // two copies drifted: ApplyRun bumped counters in both, while create/end/bootstrap
+    // only touched one. Merge the register into the MapView and clear it.
+    async fn reconcile_tournaments(&mut self) -> Response {
+        let register = match self.state.active_tournament.get().clone() {
+            Some(t) => t.into_inner(),
+            None => return Response::TournamentsReconciled { id: None, diverged: false },
+        };
+        let id = register.id;
+
+        let (merged, diverged) = match self.state.tournaments.get(&id).await.ok().flatten().map(Versioned::into_inner) {
+            Some(mut stored) => {
+                let diverged = stored.participant_count != register.participant_count
+                    || stored.total_runs != register.total_runs
+                    || stored.status != register.status
+                    || stored.end_time != register.end_time;
+                // Counters only ever grow, so the larger copy saw every run;
+                // lifecycle fields (status, end_time, ...) were only ever written to the map
+                stored.participant_count = stored.participant_count.max(register.participant_count);
+                stored.total_runs = stored.total_runs.max(register.total_runs);
+                (stored, diverged)
+            }
+            None => (register, true),
+        };
+
+        if self.state.active_tournament_id.get().is_none() && merged.status == TournamentStatus::Active {
+            self.state.active_tournament_id.set(Some(id));
+        }
+        self.state.tournaments.insert(&id, Versioned::new(merged)).unwrap();
+        self.state.active_tournament.set(None);
+
+        Response::TournamentsReconciled { id: Some(id), diverged }
+    }
+
+    // ===== Migrate Hub =====
+    // Hub chain only. Bumps the epoch so late updates from older hubs lose
+    async fn migrate_hub(&mut self, hub_chain_id: String) -> Response {
+        if !self.is_hub() {
+            return Response::Error { message: "Only the hub chain can migrate the hub".to_string() };
+        }
+        let new_hub: ChainId = match hub_chain_id.trim().parse() {
+            Ok(chain_id) => chain_id,
+            Err(_) => return Response::Error { message: "Invalid hub chain ID".to_string() },
+        };
+        let old_hub = self.runtime.chain_id();
+        if new_hub == old_hub {
+            return Response::Error { message: "Chain is already the hub".to_string() };
+        }
+        // State is not copied to the new hub: only move once nothing is left in flight
+        if let Some(reason) = self.open_hub_state().await {
+            return Response::Error { message: format!("Cannot migrate the hub while {}", reason) };
+        }
+
+        let epoch = *self.state.hub_epoch.get() + 1;
+        self.state.hub_epoch.set(epoch);
+        self.state.hub_chain_id.set(Some(new_hub.to_string()));
+        let _ = self.state.retired_hub_chains.insert(&old_hub, self.runtime.system_time());
+        let _ = self.state.retired_hub_chains.remove(&new_hub);
+
+        let retired = self.state.retired_hub_chains.indices().await.unwrap_or_default();
+        let user_chains = self.state.user_chains.indices().await.unwrap_or_default();
+        let mut notified = 0u32;
+        for chain_id in user_chains.into_iter().filter(|chain_id| *chain_id != old_hub && *chain_id != new_hub) {
+            self.runtime.send_message(chain_id, Message::SetHub { hub_chain_id: new_hub, epoch, retired: retired.clone() });
+            notified += 1;
+        }
+        self.runtime.send_message(new_hub, Message::SetHub { hub_chain_id: new_hub, epoch, retired });
+
+        Response::HubMigrated { hub_chain_id: new_hub.to_string(), epoch, notified }
+    }
+
+    /// What still needs this hub, if anything (running tournaments or unclaimed rewards)
+    async fn open_hub_state(&self) -> Option<String> {
+        let mut active = None;
+        let scan = self.state.tournaments.for_each_index_value_while(|id, tournament| {
+            if tournament.get().status == TournamentStatus::Active {
+                active = Some(id);
+            }
+            Ok(active.is_none())
+        }).await;
+        if scan.is_err() {
+            return Some("tournaments cannot be read".to_string());
+        }
+        if let Some(id) = active {
+            return Some(format!("tournament #{} is active", id));
+        }
+
+        let mut unclaimed = None;
+        let scan = self.state.rewards.for_each_index_value_while(|(tournament_id, _), reward| {
+            if !reward.claimed {
+                unclaimed = Some(tournament_id);
+            }
+            Ok(unclaimed.is_none())
+        }).await;
+        if scan.is_err() {
+            return Some("rewards cannot be read".to_string());
+        }
+        if let Some(id) = unclaimed {
+            return Some(format!("tournament #{} has unclaimed rewards", id));
+        }
+
+        let mut unclaimed = None;
+        let scan = self.state.season_rewards.for_each_index_value_while(|(season_id, _), reward| {
+            if !reward.claimed {
+                unclaimed = Some(season_id);
+            }
+            Ok(unclaimed.is_none())
+        }).await;
+        if scan.is_err() {
+            return Some("season rewards cannot be read".to_string());
+        }
+        unclaimed.map(|id| format!("season #{} has unclaimed rewards", id))
+    }
+
+    /// Explicit allow-rule for host-bound messages (see Message::allowed_origin)
+    async fn origin_allowed(&mut self, message: &Message, origin: ChainId) -> bool {
+        // A retired hub may still have messages in flight: they carry stale state
+        if self.state.retired_hub_chains.contains_key(&origin).await.unwrap_or(true) {
+            return false;
+        }
+        match message.allowed_origin() {
+            MessageOrigin::AnyChain => true,
+            MessageOrigin::TournamentShard(tournament_id) => {
+                self.state.tournament_shards.get(&tournament_id).await.ok().flatten() == Some(origin)
+            }
+            MessageOrigin::Hub => origin == self.hub_chain(),
+        }
+    }
+
+    // ===== Add / Remove Shard =====
+    // Hub chain only. Only affects tournaments created from now on
+    async fn update_shards(&mut self, chain_id: String, add: bool) -> Response {
+        if !self.is_hub() {
+            return Response::Error { message: "Shards can only be managed on the hub chain".to_string() };
+        }
+        let chain_id: ChainId = match chain_id.trim().parse() {
+            Ok(chain_id) => chain_id,
+            Err(_) => return Response::Error { message: "Invalid shard chain ID".to_string() },
+        };
+        if chain_id == self.runtime.chain_id() {
+            return Response::Error { message: "The hub cannot be its own shard".to_string() };
+        }
+
+        let mut shards = self.state.shard_chains.get().clone();
+        if add && !shards.contains(&chain_id) {
+            // The shard must know the hub before it accepts HostTournament from it
+            let update = self.hub_update().await;
+            self.runtime.send_message(chain_id, update);
+            shards.push(chain_id);
+        } else if !add {
+            shards.retain(|shard| *shard != chain_id);
+        }
+        self.state.shard_chains.set(shards.clone());
+
+        Response::ShardsUpdated { shards: shards.iter().map(|shard| shard.to_string()).collect() }
+    }
+
+    // ===== Host Tournament (message) =====
+    // On a shard: take over a tournament the hub assigned here, or take the hub's
+    // amendments to it. Run counters stay the shard's own, and once finalized here
+    // the tournament stays ended unless the hub cancelled it
+    async fn host_tournament(&mut self, mut tournament: Tournament) {
+        let id = tournament.id;
+        match self.state.tournaments.get(&id).await.ok().flatten().map(Versioned::into_inner) {
+            Some(hosted) => {
+                tournament.participant_count = hosted.participant_count;
+                tournament.total_runs = hosted.total_runs;
+                if hosted.status == TournamentStatus::Ended && tournament.status == TournamentStatus::Active {
+                    tournament.status = TournamentStatus::Ended;
+                }
+                if tournament.status != TournamentStatus::Active {
+                    let _ = self.state.leaderboard_subscribers.remove(&id);
+                }
+            }
+            None => {
+                let shard = self.runtime.chain_id();
+                let _ = self.state.tournament_shards.insert(&id, shard);
+                let _ = self.state.leaderboards.insert(&id, Vec::new());
+            }
+        }
+        let _ = self.state.tournaments.insert(&id, Versioned::new(tournament));
+    }
+
+    /// Hub: send a sharded tournament's amended record to its shard
+    async fn sync_shard(&mut self, tournament: &Tournament) {
+        let host = self.tournament_chain(tournament.id).await;
+        if host != self.runtime.chain_id() {
+            self.runtime.send_message(host, Message::HostTournament { tournament: Box::new(tournament.clone()) });
+        }
+    }
+
+    /// Hub: ask a sharded tournament's shard to finalize it (it reports back once done)
+    async fn forward_finalize(&mut self, tournament_id: u64) -> bool {
+        let host = self.tournament_chain(tournament_id).await;
+        if !self.is_hub() || host == self.runtime.chain_id() {
+            return false;
+        }
+        self.runtime.send_message(host, Message::FinalizeTournament { tournament_id });
+        true
+    }
+
+    // ===== Set Hub (message) =====
+    // Chains that were never configured take the first update; after that only newer epochs apply
+    async fn set_hub(&mut self, hub_chain_id: ChainId, epoch: u32, retired: Vec<ChainId>) {
+        if self.state.hub_chain_id.get().is_some() && epoch <= *self.state.hub_epoch.get() {
+            return;
+        }
+        self.state.hub_chain_id.set(Some(hub_chain_id.to_string()));
+        self.state.hub_epoch.set(epoch);
+        let now = self.runtime.system_time();
+        for chain_id in retired.into_iter().filter(|chain_id| *chain_id != hub_chain_id) {
+            let _ = self.state.retired_hub_chains.insert(&chain_id, now);
+        }
+    }
+
+    // ===== Migrate =====
+    // Rewrites records table by table from the stored cursor. Reads already
+    // upgrade old layouts on the fly (schema.rs); this makes storage current so
+    // older decoders can eventually be dropped
+    async fn migrate(&mut self, limit: u32) -> Response {
+        if *self.state.schema_version.get() >= CURRENT_SCHEMA_VERSION {
+            return Response::Migrated { rewritten: 0, schema_version: CURRENT_SCHEMA_VERSION, done: true };
+        }
+
+        let mut cursor = self.state.migration_cursor.get().clone();
+        let mut rewritten = 0u64;
+        while (cursor.table as usize) < MIGRATION_TABLES.len() && rewritten < limit as u64 {
+            let budget = limit as u64 - rewritten;
+            let after = cursor.last_key.as_deref();
+            let result = match cursor.table {
+                0 => rewrite_entries(&mut self.state.tournaments, after, budget).await,
+                1 => rewrite_entries(&mut self.state.players, after, budget).await,
+                2 => rewrite_entries(&mut self.state.tournament_players, after, budget).await,
+                _ => rewrite_entries(&mut self.state.runs, after, budget).await,
+            };
+            let (count, last_key) = match result {
+                Ok(progress) => progress,
+                Err(error) => {
+                    // Keep the progress so far; the failing entry is retried next time
+                    let table = MIGRATION_TABLES[cursor.table as usize];
+                    self.state.migration_cursor.set(cursor);
+                    return Response::Error { message: format!("Migrating {} failed: {}", table, error) };
+                }
+            };
+            rewritten += count;
+            if count < budget {
+                // Table exhausted
+                cursor = MigrationCursor { table: cursor.table + 1, last_key: None };
+            } else {
+                cursor.last_key = last_key;
+            }
+        }
+
+        let done = cursor.table as usize >= MIGRATION_TABLES.len();
+        if done {
+            self.state.schema_version.set(CURRENT_SCHEMA_VERSION);
+            self.state.migration_cursor.set(MigrationCursor::default());
+            self.reconcile_tournaments().await;
+        } else {
+            self.state.migration_cursor.set(cursor);
+        }
+
+        Response::Migrated { rewritten, schema_version: *self.state.schema_version.get(), done }
+    }
+
+    // ===== Helper: Finalize an overdue tournament if nobody has yet =====
+    // Called lazily by any operation, message or claim that observes now >= end_time
+    async fn finalize_if_expired(&mut self, tournament_id: u64) -> bool {
+        if self.tournament_chain(tournament_id).await != self.runtime.chain_id() {
+            return false; // Hosted on a shard
+        }
+        let tournament = match self.state.tournaments.get(&tournament_id).await.ok().flatten().map(Versioned::into_inner) {
+            Some(t) => t,
+            None => return false,
+        };
+        if tournament.status != TournamentStatus::Active || self.runtime.system_time() < tournament.end_time {
+            return false;
+        }
+        self.finalize_tournament(tournament).await;
+        true
+    }
+
+    // ===== Finalize Expired (Sweep) =====
+    // Finalizes up to `limit` overdue tournaments, oldest first
+    async fn finalize_expired(&mut self, limit: u32) -> Response {
+        let now = self.runtime.system_time();
+        let limit = limit as usize;
+        let mut overdue = Vec::new();
+        // Stop scanning as soon as the batch is full
+        self.state.tournaments.for_each_index_value_while(|id, tournament| {
+            let tournament = tournament.get();
+            if overdue.len() < limit && tournament.status == TournamentStatus::Active && now >= tournament.end_time {
+                overdue.push(id);
+            }
+            Ok(overdue.len() < limit)
+        }).await.unwrap_or_default();
+
+        let mut finalized = Vec::new();
+        for id in overdue {
+            if self.finalize_if_expired(id).await {
+                finalized.push(id);
+            } else {
+                self.forward_finalize(id).await;
+            }
+        }
+
+        Response::TournamentsFinalized { ids: finalized }
+    }
+
+    // ===== Helper: Finalize (status, then settle on the hub) =====
+    // Callers check status == Active and now >= end_time; the status flip makes it run once.
+    // A shard only ends the tournament and reports it; the hub settles when the report lands
+    async fn finalize_tournament(&mut self, mut tournament: Tournament) -> u32 {
+        let tournament_id = tournament.id;
+
+        // Mark as ended
+        tournament.status = TournamentStatus::Ended;
+        self.state.tournaments.insert(&tournament_id, Versioned::new(tournament.clone())).unwrap();
+
+        // The board is frozen: nothing more to push
+        self.state.leaderboard_subscribers.remove(&tournament_id).unwrap();
+
+        if !self.is_hub() {
+            self.report_to_directory(&tournament);
+            let board_size = self.state.leaderboards.get(&tournament_id).await.ok().flatten()
+                .map_or(0, |board| board.len());
+            return board_size.min(tournament.reward_percentages.len()) as u32;
+        }
+        self.settle_tournament(&tournament).await
+    }
+
+    // ===== Helper: Settle an ended tournament (rewards, achievements, season, ratings) =====
+    // Hub only: player, reward, season and rating state all live there
+    async fn settle_tournament(&mut self, tournament: &Tournament) -> u32 {
+        let tournament_id = tournament.id;
+
+        // Clear active tournament if this was it
+        if self.state.active_tournament_id.get() == &Some(tournament_id) {
+            self.state.active_tournament_id.set(None);
+        }
+
+        // Get leaderboard and create rewards along the tournament's reward curve
+        let leaderboard = self.state.leaderboards.get(&tournament_id).await.ok().flatten()
+            .unwrap_or_default();
+
+        let reward_percentages = &tournament.reward_percentages;
+        let mut winner_count = 0u32;
+
+        for (i, entry) in leaderboard.iter().take(reward_percentages.len()).enumerate() {
+            let xp_amount = (tournament.xp_reward_pool * reward_percentages[i]) / 100;
+            
+            let reward = TournamentReward {
+                tournament_id,
+                wallet_address: entry.wallet_address,
+                rank: entry.rank,
+                xp_amount,
+                claimed: false,
+            };
+
+            let key = (tournament_id, entry.wallet_address);
+            self.state.rewards.insert(&key, reward).unwrap();
+
+            // Update player's tournaments_won for 1st place
+            if i == 0 {
+                if let Some(mut player) = self.state.players.get(&entry.wallet_address).await.ok().flatten().map(Versioned::into_inner) {
+                    player.tournaments_won += 1;
+                    self.state.players.insert(&entry.wallet_address, Versioned::new(player)).unwrap();
+                }
+            }
+            
+            winner_count += 1;
+        }
+
+        // Unlock placement achievements over the whole board (the curve may pay fewer ranks)
+        for (wallet, achievement) in placement_achievements(&leaderboard) {
+            self.unlock_achievement(wallet, achievement, tournament_id, None).await;
+        }
+
+        // Award season points from final placements
+        if let Some(season_id) = tournament.season_id {
+            self.award_season_points(season_id, &leaderboard).await;
+        }
+
+        // Update skill ratings from final standings
+        self.update_ratings(&leaderboard).await;
+
+        winner_count
+    }
+
+    // ===== Helper: Award season points from a final tournament leaderboard =====
+    // Only players who finished the maze score; ignored once the season has ended
+    async fn award_season_points(&mut self, season_id: u64, leaderboard: &[LeaderboardEntry]) {
+        match self.state.seasons.get(&season_id).await.ok().flatten() {
+            Some(season) if season.status == SeasonStatus::Active => {}
+            _ => return,
+        }
+
+        let mut standings = self.state.season_standings.get(&season_id).await.ok().flatten()
+            .unwrap_or_default();
+
+        for entry in leaderboard {
+            let points = season_points_for_rank(entry.rank);
+            if points == 0 || entry.best_time_ms == u64::MAX {
+                continue;
+            }
+
+            match standings.iter_mut().find(|s| s.wallet_address == entry.wallet_address) {
+                Some(standing) => {
+                    standing.points += points;
+                    standing.tournaments_scored += 1;
+                }
+                None => standings.push(SeasonStanding {
+                    rank: 0,
+                    wallet_address: entry.wallet_address,
+                    username: entry.username.clone(),
+                    points,
+                    carried_points: 0,
+                    tournaments_scored: 1,
+                }),
+            }
+        }
+
+        // Sort by points (descending - higher is better)
+        standings.sort_by_key(|s| std::cmp::Reverse(s.points));
+        for (i, standing) in standings.iter_mut().enumerate() {
+            standing.rank = (i + 1) as u32;
+        }
+
+        self.state.season_standings.insert(&season_id, standings).unwrap();
+    }
+
+    // ===== Helper: Update skill ratings from a final tournament leaderboard =====
+    // Only players who finished the maze are rated; needs at least two of them
+    async fn update_ratings(&mut self, leaderboard: &[LeaderboardEntry]) {
+        let mut players = Vec::new();
+        for entry in leaderboard.iter().filter(|e| e.best_time_ms < u64::MAX) {
+            if let Some(player) = self.state.players.get(&entry.wallet_address).await.ok().flatten().map(Versioned::into_inner) {
+                players.push((player, entry.best_time_ms));
+            }
+        }
+        if players.len() < 2 {
+            return;
+        }
+
+        let standings: Vec<(u32, u32, u64)> = players.iter()
+            .map(|(p, time)| (p.rating, p.rating_deviation, *time))
+            .collect();
+        let updated = glicko_update(&standings);
+
+        let mut ratings = self.state.rating_leaderboard.get().clone();
+        for ((mut player, _), (rating, rating_deviation)) in players.into_iter().zip(updated) {
+            player.rating = rating;
+            player.rating_deviation = rating_deviation;
+            player.rated_tournaments += 1;
+            player.rating_provisional = rating_deviation > PROVISIONAL_RATING_DEVIATION;
+
+            ratings.retain(|e| e.wallet_address != player.wallet_address);
+            ratings.push(RatingEntry {
+                rank: 0,
+                wallet_address: player.wallet_address,
+                username: player.username.clone(),
+                rating,
+                rating_deviation,
+                provisional: player.rating_provisional,
+            });
+
+            let wallet = player.wallet_address;
+            self.state.players.insert(&wallet, Versioned::new(player)).unwrap();
+        }
+
+        // Sort by rating (descending - higher is better)
+        ratings.sort_by_key(|e| std::cmp::Reverse(e.rating));
+        ratings.truncate(100);
+        for (i, entry) in ratings.iter_mut().enumerate() {
+            entry.rank = (i + 1) as u32;
+        }
+        self.state.rating_leaderboard.set(ratings);
+    }
+
+    // ===== Create Season =====
+    // Hub chain only: seasons group the hub's tournaments
+    // Only one season can be active at a time
+    // Soft reset: the previous season's final points carry over at SEASON_CARRYOVER_PERCENT
+    async fn create_season(
+        &mut self,
+        title: String,
+        duration_days: u64,
+        xp_reward_pool: u64,
+    ) -> Response {
+        if !self.is_hub() {
+            return Response::Error { message: "Seasons can only be created on the hub chain".to_string() };
+        }
+        if let Some(active_id) = *self.state.active_season_id.get() {
+            return Response::Error {
+                message: format!("Season {} is still active. End it first.", active_id),
+            };
+        }
+
+        let now = self.runtime.system_time();
+        let end_time = match duration_days.checked_mul(24 * 60 * 60 * 1_000_000)
+            .and_then(|duration_micros| now.micros().checked_add(duration_micros))
+        {
+            Some(end) => linera_sdk::linera_base_types::Timestamp::from(end),
+            None => return Response::Error { message: "Season duration is too long".to_string() },
+        };
+
+        // Deployments that predate seasons have next_season_id = 0
+        let id = std::cmp::max(*self.state.next_season_id.get(), 1);
+        self.state.next_season_id.set(id + 1);
+
+        // Soft reset: seed standings from the previous season
+        let previous = self.state.season_standings.get(&(id - 1)).await.ok().flatten()
+            .unwrap_or_default();
+        let mut standings: Vec<SeasonStanding> = previous.into_iter()
+            .map(|s| {
+                let carried = s.points * SEASON_CARRYOVER_PERCENT / 100;
+                SeasonStanding {
+                    rank: s.rank,
+                    wallet_address: s.wallet_address,
+                    username: s.username,
+                    points: carried,
+                    carried_points: carried,
+                    tournaments_scored: 0,
+                }
+            })
+            .filter(|s| s.points > 0)
+            .collect();
+        for (i, standing) in standings.iter_mut().enumerate() {
+            standing.rank = (i + 1) as u32;
+        }
+        let carried_over = standings.len() as u32;
+
+        let season = Season {
+            id,
+            title,
+            start_time: now,
+            end_time,
+            status: SeasonStatus::Active,
+            tournament_ids: Vec::new(),
+            xp_reward_pool,
+            created_at: now,
+        };
+
+        self.state.seasons.insert(&id, season).unwrap();
+        self.state.season_standings.insert(&id, standings).unwrap();
+        self.state.active_season_id.set(Some(id));
+
+        Response::SeasonCreated {
+            id,
+            end_time,
+            carried_over,
+        }
+    }
+
+    // ===== End Season (Finalize) =====
+    // Same rules as end_tournament: anyone can call once now >= end_time
+    async fn end_season(
+        &mut self,
+        _caller: AccountOwner,
+        season_id: u64,
+    ) -> Response {
+        let mut season = match self.state.seasons.get(&season_id).await.ok().flatten() {
+            Some(s) => s,
+            None => return Response::Error { message: "Season not found".to_string() },
+        };
+
+        if season.status == SeasonStatus::Ended {
+            return Response::Error { message: "Season already ended".to_string() };
+        }
+
+        let now = self.runtime.system_time();
+        if now < season.end_time {
+            return Response::Error {
+                message: format!(
+                    "Season cannot be finalized yet. Ends at timestamp {}",
+                    season.end_time.micros()
+                )
+            };
+        }
+
+        // Mark as ended
+        season.status = SeasonStatus::Ended;
+        self.state.seasons.insert(&season_id, season.clone()).unwrap();
+
+        if self.state.active_season_id.get() == &Some(season_id) {
+            self.state.active_season_id.set(None);
+        }
+
+        let standings = self.state.season_standings.get(&season_id).await.ok().flatten()
+            .unwrap_or_default();
+
+        // Reward distribution: same split as tournaments
+        let reward_percentages = DEFAULT_REWARD_PERCENTAGES;
+        let mut winner_count = 0u32;
+
+        for (i, standing) in standings.iter().take(reward_percentages.len()).enumerate() {
+            let reward = SeasonReward {
+                season_id,
+                wallet_address: standing.wallet_address,
+                rank: standing.rank,
+                xp_amount: (season.xp_reward_pool * reward_percentages[i]) / 100,
+                claimed: false,
+            };
+
+            let key = (season_id, standing.wallet_address);
+            self.state.season_rewards.insert(&key, reward).unwrap();
+            winner_count += 1;
+        }
+
+        Response::SeasonEnded {
+            id: season_id,
+            winner_count,
+        }
+    }
+
+    // ===== Claim Season Reward =====
+    async fn claim_season_reward(
+        &mut self,
+        signer: AccountOwner,
+        season_id: u64,
+    ) -> Response {
+        let wallet = match self.get_wallet_for_signer(&signer).await {
+            Some(w) => w,
+            None => return Response::Error { message: "Player not registered".to_string() },
+        };
+
+        let key = (season_id, wallet);
+        let mut reward = match self.state.season_rewards.get(&key).await.ok().flatten() {
+            Some(r) => r,
+            None => return Response::Error { message: "No reward found for this season".to_string() },
+        };
+
+        if reward.claimed {
+            return Response::Error { message: "Reward already claimed".to_string() };
+        }
+
+        reward.claimed = true;
+        self.state.season_rewards.insert(&key, reward.clone()).unwrap();
+
+        if let Some(mut player) = self.state.players.get(&wallet).await.ok().flatten().map(Versioned::into_inner) {
+            player.total_xp += reward.xp_amount;
+            self.state.players.insert(&wallet, Versioned::new(player)).unwrap();
+        }
+
+        Response::SeasonRewardClaimed {
+            season_id,
+            xp_amount: reward.xp_amount,
+        }
+    }
+
+    // ===== Claim Reward =====
+    async fn claim_reward(
+        &mut self,
+        signer: AccountOwner,
+        tournament_id: u64,
+    ) -> Response {
+        // Get wallet for signer
+        let wallet = match self.get_wallet_for_signer(&signer).await {
+            Some(w) => w,
+            None => return Response::Error { message: "Player not registered".to_string() },
+        };
+
+        let key = (tournament_id, wallet);
+        let mut reward = match self.state.rewards.get(&key).await.ok().flatten() {
+            Some(r) => r,
+            None => return Response::Error { message: "No reward found for this tournament".to_string() },
+        };
+
+        if reward.claimed {
+            return Response::Error { message: "Reward already claimed".to_string() };
+        }
+
+        // Mark as claimed
+        reward.claimed = true;
+        self.state.rewards.insert(&key, reward.clone()).unwrap();
+
+        // Add XP to player
+        if let Some(mut player) = self.state.players.get(&wallet).await.ok().flatten().map(Versioned::into_inner) {
+            player.total_xp += reward.xp_amount;
+            self.state.players.insert(&wallet, Versioned::new(player)).unwrap();
+        }
+
+        Response::RewardClaimed {
+            tournament_id,
+            xp_amount: reward.xp_amount,
+        }
+    }
+    
+    // ===== Helper: Resolve signer to (wallet, username) =====
+    // Auto-binds Address20 signers (EVM wallets) and auto-registers the player
+    async fn resolve_player(&mut self, signer: AccountOwner) -> Result<([u8; 20], String), Response> {
+        let wallet = match self.get_wallet_for_signer(&signer).await {
+            Some(w) => w,
+            None => match &signer {
+                AccountOwner::Address20(addr) => {
+                    self.state.signer_to_wallet.insert(&signer, *addr).unwrap();
+                    *addr
+                },
+                _ => return Err(Response::Error {
+                    message: "Player not registered. Call registerPlayer first.".to_string()
+                }),
+            },
+        };
+
+        let player = self.get_or_create_player(signer, wallet, "").await;
+        Ok((wallet, player.username))
+    }
+
+    /// Tournament admins: its organizer, or whoever can propose blocks on the hub chain
+    fn can_administer(&mut self, caller: AccountOwner, tournament: &Tournament) -> bool {
+        self.is_hub() || tournament.organizer == Some(caller)
+    }
+
+    /// Chain entropy for a new tournament's seed
+    fn seed_entropy(&mut self, tournament_id: u64) -> [u8; 32] {
+        let chain_id = self.runtime.chain_id().to_string();
+        let application_id = self.runtime.application_id().forget_abi().to_string();
+        let block_height = self.runtime.block_height().0;
+        let micros = self.runtime.system_time().micros();
+        compute_seed_entropy(&chain_id, &application_id, block_height, micros, tournament_id)
+    }
+
+    /// Drop a wallet's expired tickets; returns the nonces still open
+    async fn prune_expired_tickets(&mut self, wallet: [u8; 20], now: Timestamp) -> Vec<u64> {
+        let nonces = self.state.open_run_tickets.get(&wallet).await.ok().flatten().unwrap_or_default();
+        let mut open = Vec::with_capacity(nonces.len());
+        for nonce in nonces {
+            match self.state.run_tickets.get(&(wallet, nonce)).await.ok().flatten() {
+                Some(ticket) if !ticket.expired(now) => open.push(nonce),
+                Some(_) => {
+                    let _ = self.state.run_tickets.remove(&(wallet, nonce));
+                }
+                None => {}
+            }
+        }
+        open
+    }
+
+    // ===== Helper: Send a message to the hub chain =====
+    fn send_to_hub(&mut self, message: Message) {
+        let hub_chain = self.hub_chain();
+        self.runtime.send_message(hub_chain, message);
+    }
+
+    /// Send a wallet's current state to the chains subscribed to it (hub only)
+    async fn push_snapshot(&mut self, wallet: [u8; 20], tournament_id: Option<u64>) {
+        let subscribers = match self.state.snapshot_subscribers.get(&wallet).await.ok().flatten() {
+            Some(chains) if !chains.is_empty() => chains,
+            _ => return,
+        };
+        let player = match self.state.players.get(&wallet).await.ok().flatten().map(Versioned::into_inner) {
+            Some(p) => Box::new(p),
+            None => return,
+        };
+
+        let (mut tournament, mut tournament_player, mut rank) = (None, None, None);
+        if let Some(id) = tournament_id {
+            tournament = self.state.tournaments.get(&id).await.ok().flatten()
+                .map(|t| Box::new(t.into_inner()));
+            tournament_player = self.state.tournament_players.get(&(id, wallet)).await.ok().flatten()
+                .map(|tp| Box::new(tp.into_inner()));
+            rank = self.state.leaderboards.get(&id).await.ok().flatten()
+                .and_then(|entries| entries.iter().find(|e| e.wallet_address == wallet).map(|e| e.rank));
+        }
+
+        let hub = self.runtime.chain_id();
+        for chain_id in subscribers.into_iter().filter(|chain_id| *chain_id != hub) {
+            self.runtime.send_message(chain_id, Message::PlayerSnapshot {
+                wallet_address: wallet,
+                player: player.clone(),
+                tournament: tournament.clone(),
+                tournament_player: tournament_player.clone(),
+                rank,
+            });
+        }
+    }
+
+    /// Store a tournament's leaderboard and push what changed to subscribed chains;
+    /// returns the changes (a shard reports them to the hub)
+    async fn save_leaderboard(
+        &mut self,
+        tournament_id: u64,
+        leaderboard: Vec<LeaderboardEntry>,
+    ) -> (Vec<LeaderboardChange>, Vec<[u8; 20]>) {
+        let old = self.state.leaderboards.get(&tournament_id).await.ok().flatten().unwrap_or_default();
+        let (changes, removed) = leaderboard_changes(&old, &leaderboard);
+        let subscribers = self.state.leaderboard_subscribers.get(&tournament_id).await.ok().flatten()
+            .unwrap_or_default();
+        if !changes.is_empty() || !removed.is_empty() {
+            for chain_id in subscribers {
+                self.runtime.send_message(chain_id, Message::LeaderboardDelta {
+                    tournament_id,
+                    changes: changes.clone(),
+                    removed: removed.clone(),
+                    reset: false,
+                });
+            }
+        }
+        let _ = self.state.leaderboards.insert(&tournament_id, leaderboard);
+        self.bump_leaderboard_version(tournament_id).await;
+        (changes, removed)
+    }
+
+    /// Advance the cursor the service's leaderboardUpdates subscription compares against
+    async fn bump_leaderboard_version(&mut self, tournament_id: u64) {
+        let version = self.state.leaderboard_versions.get(&tournament_id).await.ok().flatten()
+            .unwrap_or_default();
+        let _ = self.state.leaderboard_versions.insert(&tournament_id, version + 1);
+    }
+
+    /// Store a snapshot pushed by the hub in this chain's cache (user chains)
+    async fn apply_snapshot(
+        &mut self,
+        wallet: [u8; 20],
+        player: Player,
+        tournament: Option<Tournament>,
+        tournament_player: Option<TournamentPlayer>,
+        rank: Option<u32>,
+    ) {
+        if player.wallet_address == wallet {
+            let _ = self.state.username_to_wallet.insert(&player.username, wallet);
+            let _ = self.state.players.insert(&wallet, Versioned::new(player));
+        }
+        if let Some(tournament) = tournament {
+            let id = tournament.id;
+            let key = (id, wallet);
+            if let Some(tp) = tournament_player {
+                let _ = self.state.tournament_players.insert(&key, Versioned::new(tp));
+            }
+            let _ = match rank {
+                Some(rank) => self.state.cached_ranks.insert(&key, rank),
+                None => self.state.cached_ranks.remove(&key),
+            };
+            let _ = self.state.tournaments.insert(&id, Versioned::new(tournament));
+        }
+        let _ = self.state.snapshot_synced_at.insert(&wallet, self.runtime.system_time());
+    }
+
+    /// Record the hub's answer to a submitted run (first answer wins)
+    async fn resolve_receipt(
+        &mut self,
+        receipt_id: u64,
+        status: ReceiptStatus,
+        run_id: Option<u64>,
+        xp_earned: u64,
+        rank: Option<u32>,
+        reason: Option<String>,
+    ) {
+        let mut receipt = match self.state.run_receipts.get(&receipt_id).await.ok().flatten() {
+            Some(r) if r.status == ReceiptStatus::Pending => r,
+            _ => return,
+        };
+        receipt.status = status;
+        receipt.run_id = run_id;
+        receipt.xp_earned = xp_earned;
+        receipt.rank = rank;
+        receipt.reason = reason;
+        receipt.resolved_at = Some(self.runtime.system_time());
+        let _ = self.state.run_receipts.insert(&receipt_id, receipt);
+    }
+
+    /// Chain authoritative for a tournament: its shard, or the hub if unsharded or unknown here
+    async fn tournament_chain(&mut self, tournament_id: u64) -> ChainId {
+        match self.state.tournament_shards.get(&tournament_id).await.ok().flatten() {
+            Some(chain_id) => chain_id,
+            None => self.hub_chain(),
+        }
+    }
+
+    /// Receipts are answered by the chain the run went to: the tournament's host, or the hub
+    async fn receipt_from_host(&mut self, receipt_id: u64, origin: Option<ChainId>) -> bool {
+        let Some(origin) = origin else { return false };
+        if origin == self.hub_chain() {
+            return true;
+        }
+        match self.state.run_receipts.get(&receipt_id).await.ok().flatten() {
+            Some(receipt) => self.tournament_chain(receipt.tournament_id).await == origin,
+            None => false,
+        }
+    }
+
+    /// Shards tell the hub when a hosted tournament is finalized
+    fn report_to_directory(&mut self, tournament: &Tournament) {
+        if self.is_hub() {
+            return;
+        }
+        self.send_to_hub(Message::ShardTournamentUpdate { tournament: Box::new(tournament.clone()) });
+    }
+
+    /// The configured hub chain; chains that were never told fall back to the creator chain
+    fn hub_chain(&mut self) -> ChainId {
+        match self.state.hub_chain_id.get().as_deref().and_then(|id| id.parse().ok()) {
+            Some(chain_id) => chain_id,
+            None => self.runtime.application_creator_chain_id(),
+        }
+    }
+
+    fn is_hub(&mut self) -> bool {
+        self.runtime.chain_id() == self.hub_chain()
+    }
+
+    /// SetHub message describing this chain's view of the hub
+    async fn hub_update(&mut self) -> Message {
+        let retired = self.state.retired_hub_chains.indices().await.unwrap_or_default();
+        Message::SetHub { hub_chain_id: self.hub_chain(), epoch: *self.state.hub_epoch.get(), retired }
+    }
+
+    // ===== Helper: Apply a player's runs to one tournament =====
+    // Each run is checked and recorded on its own; the tournament player, player,
+    // leaderboards and tournament are then updated once for the whole batch.
+    // Returns one result per run (in order) and the player's rank afterwards.
+    async fn apply_runs(
+        &mut self,
+        wallet_address: [u8; 20],
+        username: String,
+        tournament_id: u64,
+        runs: Vec<RunSubmission>,
+    ) -> (Vec<RunResult>, Option<u32>) {
+        let now = self.runtime.system_time();
+
+        // ===== STEP 1: Load tournament from the MapView (single source of truth) =====
+        let tournament = self.state.tournaments.get(&tournament_id).await.ok().flatten().map(Versioned::into_inner);
+        let closed = match &tournament {
+            None => Some("Unknown tournament"),
+            Some(t) if t.status != TournamentStatus::Active => Some("Tournament is not active"),
+            Some(t) if !t.seed_revealed => Some("Tournament seed has not been revealed"),
+            Some(t) if now >= t.end_time => Some("Tournament has ended"),
+            Some(_) => None,
+        };
+        let mut tournament = match (tournament, closed) {
+            (Some(t), None) => t,
+            (_, reason) => {
+                let reason = reason.unwrap_or_default().to_string();
+                return (vec![RunResult::rejected(reason); runs.len()], None);
+            }
+        };
+
+        // ===== STEP 2: Get or create tournament player =====
+        let key = (tournament_id, wallet_address);
+        let existing = self.state.tournament_players.get(&key).await.ok().flatten().map(Versioned::into_inner);
+        let is_new_participant = existing.is_none();
+        let mut tp = existing.unwrap_or_else(|| TournamentPlayer {
+            wallet_address,
+            username: username.clone(),
+            best_time_ms: u64::MAX,
+            best_score: 0,
+            total_runs: 0,
+            total_xp_earned: 0,
+            last_run_at: now,
+            joined_at: now,
+            best_splits: Vec::new(),
+            sum_of_best_ms: None,
+            ghost_run_id: None,
+        });
+
+        // ===== STEP 3: Record each run and fold it into the in-memory stats =====
+        let mut results = Vec::with_capacity(runs.len());
+        let mut accepted: Vec<GameRun> = Vec::new();
+        let mut improved_stages: Vec<u32> = Vec::new();
+        let mut best_run: Option<(GameRun, Option<Vec<u8>>)> = None;
+        for submission in runs {
+            let run = match self.record_run(&tournament, wallet_address, &username, &submission, now).await {
+                Ok(run) => run,
+                Err(reason) => {
+                    results.push(RunResult::rejected(reason));
+                    continue;
+                }
+            };
+            results.push(RunResult::accepted(run.id, run.xp_earned));
+            tournament.total_runs += 1;
+
+            // Tournament player stats
+            if run.completed && run.ranked_time_ms < tp.best_time_ms {
+                tp.best_time_ms = run.ranked_time_ms;
+                best_run = Some((run.clone(), submission.ghost));
+            }
+            if run.score > tp.best_score {
+                tp.best_score = run.score;
+            }
+            tp.total_runs += 1;
+            tp.total_xp_earned += run.xp_earned;
+            tp.last_run_at = now;
+
+            // Per-stage bests (only stages actually cleared in this run)
+            for split in cleared_splits(&run.splits, run.completed) {
+                let index = (split.stage - 1) as usize;
+                if tp.best_splits.len() <= index {
+                    tp.best_splits.resize(index + 1, u64::MAX);
+                }
+                if split.time_ms < tp.best_splits[index] {
+                    tp.best_splits[index] = split.time_ms;
+                    if !improved_stages.contains(&split.stage) {
+                        improved_stages.push(split.stage);
+                    }
+                }
+            }
+
+            accepted.push(run);
+        }
+        if accepted.is_empty() {
+            return (results, None);
+        }
+
+        // ===== STEP 4: Participant counts and recent runs =====
+        if is_new_participant {
+            tournament.participant_count += 1;
+            let mut participants = self.state.tournament_participants.get(&tournament_id).await.ok().flatten()
+                .unwrap_or_default();
+            participants.push(wallet_address);
+            let _ = self.state.tournament_participants.insert(&tournament_id, participants);
+        }
+        let mut recent: Vec<u64> = accepted.iter().rev().map(|run| run.id).collect();
+        recent.extend(self.state.recent_runs.get().iter().copied());
+        recent.truncate(100);
+        self.state.recent_runs.set(recent);
+
+        // ===== STEP 5: Store tournament player =====
+        // Only once every stage of the maze has a best split
+        let all_stages = tp.best_splits.len() == tournament.maze_config.stages as usize;
+        if !improved_stages.is_empty() && all_stages && tp.best_splits.iter().all(|t| *t < u64::MAX) {
+            tp.sum_of_best_ms = Some(tp.best_splits.iter().sum());
+        }
+        let _ = self.state.tournament_players.insert(&key, Versioned::new(tp.clone()));
+
+        // ===== STEP 6: Update leaderboard =====
+        let mut leaderboard = self.state.leaderboards
+            .get(&tournament_id)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+
+        // Find or update entry
+        let mut found = false;
+        for entry in &mut leaderboard {
+            if entry.wallet_address == wallet_address {
+                if tp.best_time_ms < entry.best_time_ms {
+                    entry.best_time_ms = tp.best_time_ms;
+                }
+                if tp.best_score > entry.best_score {
+                    entry.best_score = tp.best_score;
+                }
+                entry.total_runs = tp.total_runs;
+                entry.total_xp = tp.total_xp_earned;
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            leaderboard.push(LeaderboardEntry {
+                wallet_address,
+                username: username.clone(),
+                best_time_ms: tp.best_time_ms,
+                best_score: tp.best_score,
+                total_runs: tp.total_runs,
+                total_xp: tp.total_xp_earned,
+                rank: 0,
+            });
+        }
+
+        // Sort by best time (ascending - lower is better)
+        leaderboard.sort_by(|a, b| a.best_time_ms.cmp(&b.best_time_ms));
+
+        // Update ranks
+        for (i, entry) in leaderboard.iter_mut().enumerate() {
+            entry.rank = (i + 1) as u32;
+        }
+
+        // Keep top 100
+        if leaderboard.len() > 100 {
+            leaderboard.truncate(100);
+        }
+
+        // Player's rank for the delivery receipts (None outside the top 100)
+        let rank = leaderboard.iter()
+            .find(|e| e.wallet_address == wallet_address)
+            .map(|e| e.rank);
+
+        // ===== STEP 6a: Ghost replays (top-N personal bests only) =====
+        // Only the batch's best run can hold the player's ghost slot
+        if let Some((run, ghost)) = best_run {
+            if let (Some(data), Some(rank)) = (ghost, rank) {
+                if rank <= GHOST_LEADERBOARD_SLOTS && data.len() <= MAX_GHOST_BYTES {
+                    self.store_ghost(&run, data).await;
+                }
+            }
+            self.prune_ghosts(tournament_id, &leaderboard).await;
+        }
+
+        // Store leaderboard in MapView (and push the changes to subscribers)
+        let (changes, removed) = self.save_leaderboard(tournament_id, leaderboard).await;
+
+        // ===== STEP 6b: Stage and sum-of-best leaderboards =====
+        if let (Some(sum_of_best), false) = (tp.sum_of_best_ms, improved_stages.is_empty()) {
+            let mut board = self.state.sum_of_best_leaderboards.get(&tournament_id).await.ok().flatten()
+                .unwrap_or_default();
+            upsert_split_entry(&mut board, wallet_address, &username, sum_of_best);
+            let _ = self.state.sum_of_best_leaderboards.insert(&tournament_id, board);
+        }
+        for stage in improved_stages {
+            let stage_key = (tournament_id, stage);
+            let mut board = self.state.stage_leaderboards.get(&stage_key).await.ok().flatten()
+                .unwrap_or_default();
+            let best = tp.best_splits[(stage - 1) as usize];
+            upsert_split_entry(&mut board, wallet_address, &username, best);
+            let _ = self.state.stage_leaderboards.insert(&stage_key, board);
+        }
+
+        // ===== STEP 7: Persist tournament counters =====
+        let _ = self.state.tournaments.insert(&tournament_id, Versioned::new(tournament.clone()));
+
+        // ===== STEP 8: Credit the player on the hub (a shard reports the batch there) =====
+        if self.is_hub() {
+            self.credit_runs(&tournament, wallet_address, &username, is_new_participant, &accepted).await;
+        } else {
+            self.send_to_hub(Message::ShardRunsApplied {
+                wallet_address,
+                username,
+                tournament_id,
+                participant_count: tournament.participant_count,
+                total_runs: tournament.total_runs,
+                new_participant: is_new_participant,
+                tournament_player: Box::new(tp),
+                runs: accepted,
+                changes,
+                removed,
+            });
+        }
+        (results, rank)
+    }
+
+    // ===== Helper: Credit a player's accepted runs (hub only) =====
+    // Player stats, run achievements, clan XP and team boards; runs applied
+    // on a shard arrive here through ShardRunsApplied
+    async fn credit_runs(
+        &mut self,
+        tournament: &Tournament,
+        wallet_address: [u8; 20],
+        username: &str,
+        new_participant: bool,
+        runs: &[GameRun],
+    ) {
+        let now = self.runtime.system_time();
+        let tournament_id = tournament.id;
+        let mut player = self.state.players.get(&wallet_address).await.ok().flatten().map(Versioned::into_inner)
+            .unwrap_or_else(|| Player {
+                wallet_address,
+                username: username.to_string(),
+                total_xp: 0,
+                total_runs: 0,
+                tournaments_played: 0,
+                tournaments_won: 0,
+                best_time_ms: None,
+                registered_at: now,
+                last_active: now,
+                rating: DEFAULT_RATING,
+                rating_deviation: DEFAULT_RATING_DEVIATION,
+                rated_tournaments: 0,
+                rating_provisional: true,
+                duels_played: 0,
+                duels_won: 0,
+            });
+
+        // Global player stats, with the player as of each run for its achievements
+        let mut snapshots: Vec<Player> = Vec::with_capacity(runs.len());
+        let mut xp_total = 0;
+        for run in runs {
+            xp_total += run.xp_earned;
+            player.total_xp += run.xp_earned;
+            player.total_runs += 1;
+            player.last_active = run.created_at;
+            if run.completed {
+                match player.best_time_ms {
+                    Some(best) if run.time_ms < best => player.best_time_ms = Some(run.time_ms),
+                    None => player.best_time_ms = Some(run.time_ms),
+                    _ => {}
+                }
+            }
+            snapshots.push(player.clone());
+        }
+        if new_participant {
+            player.tournaments_played += 1;
+        }
+        let _ = self.state.players.insert(&wallet_address, Versioned::new(player));
+
+        // Unlock run-based achievements
+        // Each run is checked against the player as it stood right after that run
+        for (run, snapshot) in runs.iter().zip(&snapshots) {
+            for definition in ACHIEVEMENTS {
+                if definition.criterion.is_met_by_run(tournament.difficulty, run, snapshot) {
+                    self.unlock_achievement(wallet_address, definition.id, tournament_id, Some(run.id)).await;
+                }
+            }
+        }
+
+        // Clan XP and team leaderboard
+        if let Some(clan_id) = self.state.wallet_to_clan.get(&wallet_address).await.ok().flatten() {
+            let key = (tournament_id, wallet_address);
+            self.credit_clan_xp(clan_id, wallet_address, xp_total).await;
+            let mut credits = self.state.tournament_clan_xp.get(&key).await.ok().flatten()
+                .unwrap_or_default();
+            match credits.iter_mut().find(|c| c.clan_id == clan_id) {
+                Some(credit) => credit.xp += xp_total,
+                None => credits.push(ClanXpCredit { clan_id, xp: xp_total }),
+            }
+            let _ = self.state.tournament_clan_xp.insert(&key, credits);
+            if tournament.team_mode != TeamMode::Individual {
+                self.update_team_leaderboard(tournament, clan_id).await;
+            }
+        }
+
+        // Refresh subscribed user-chain caches
+        self.push_snapshot(wallet_address, Some(tournament_id)).await;
+    }
+
+    // ===== Helper: Validate one run, burn its ticket and store the run record =====
+    async fn record_run(
+        &mut self,
+        tournament: &Tournament,
+        wallet_address: [u8; 20],
+        username: &str,
+        run: &RunSubmission,
+        now: Timestamp,
+    ) -> Result<GameRun, String> {
+        if run.tournament_id != tournament.id {
+            return Err("Run is for a different tournament".to_string());
+        }
+        validate_splits(&run.splits, run.time_ms, run.deaths, run.coins)?;
+        // Must fit the tournament's maze
+        tournament.maze_config.check_run(run.time_ms, run.coins, run.completed, &run.splits)?;
+
+        // Run ticket: must be unexpired and older than the claimed time
+        let ticket_key = (wallet_address, run.ticket_nonce);
+        let ticket = match self.state.run_tickets.get(&ticket_key).await.ok().flatten() {
+            Some(t) => t,
+            None => return Err("No run ticket for this nonce".to_string()),
+        };
+        ticket.validate(tournament.id, run.time_ms, now)?;
 
-        let now = self.runtime.system_time();
+        // Seed this attempt played, and its time scaled to the reference maze
+        let run_seed = ticket.attempt_seed.clone();
+        let (path_length, ranked_time_ms) = match tournament.seed_mode {
+            SeedMode::Shared => (tournament.reference_path_length, run.time_ms),
+            SeedMode::PerAttempt => {
+                let length = maze_path_length(&run_seed, &tournament.maze_config);
+                (length, normalize_time(run.time_ms, length, tournament.reference_path_length))
+            }
+        };
 
-        let player = Player {
-            wallet_address,
-            username: username.clone(),
-            total_xp: 0,
-            total_runs: 0,
-            tournaments_played: 0,
-            tournaments_won: 0,
-            best_time_ms: None,
-            registered_at: now,
-            last_active: now,
+        // Commit-reveal: a reveal must match the pending commitment
+        let commitment_key = (tournament.id, wallet_address);
+        let verified = match &run.reveal {
+            Some(reveal) => {
+                let commitment = match self.state.run_commitments.get(&commitment_key).await.ok().flatten() {
+                    Some(c) => c,
+                    None => return Err("No run commitment to reveal".to_string()),
+                };
+                commitment.verify(&run_seed, reveal, run.time_ms, now)?;
+                let _ = self.state.run_commitments.remove(&commitment_key);
+                true
+            }
+            None if tournament.commit_reveal_required => {
+                return Err("Tournament requires a commit-reveal run".to_string());
+            }
+            None => false,
         };
 
-        // Store everything
-        self.state.players.insert(&wallet_address, player).unwrap();
-        self.state.username_to_wallet.insert(&username, wallet_address).unwrap();
-        self.state.signer_to_wallet.insert(&signer, wallet_address).unwrap();
+        // All checks passed - burn the ticket
+        let _ = self.state.run_tickets.remove(&ticket_key);
+        if let Some(mut open) = self.state.open_run_tickets.get(&wallet_address).await.ok().flatten() {
+            open.retain(|nonce| *nonce != run.ticket_nonce);
+            let _ = self.state.open_run_tickets.insert(&wallet_address, open);
+        }
 
-        Response::PlayerRegistered { wallet_address }
+        let run_id = *self.state.next_run_id.get();
+        self.state.next_run_id.set(run_id + 1);
+        let record = GameRun {
+            id: run_id,
+            tournament_id: tournament.id,
+            wallet_address,
+            username: username.to_string(),
+            time_ms: run.time_ms,
+            score: run.score,
+            coins: run.coins,
+            deaths: run.deaths,
+            completed: run.completed,
+            xp_earned: tournament.difficulty.calculate_xp(run.time_ms, run.deaths, run.completed),
+            created_at: now,
+            splits: run.splits.clone(),
+            verified,
+            ticket_id: ticket.id,
+            ticket_nonce: run.ticket_nonce,
+            maze_seed: run_seed,
+            path_length,
+            ranked_time_ms,
+            ticket_entropy: ticket.entropy,
+        };
+        let _ = self.state.runs.insert(&run_id, Versioned::new(record.clone()));
+        Ok(record)
     }
 
-    // ===== Submit Run (PRIMARY OPERATION) =====
-    // CRITICAL: This function ALWAYS sends a message to hub chain.
-    // NO branching logic. NO direct state mutation.
-    // All state changes happen ONLY in execute_message.
-    async fn submit_run(
-        &mut self,
-        signer: AccountOwner,
-        tournament_id: u64,
-        time_ms: u64,
-        score: u64,
-        coins: u32,
-        deaths: u32,
-        completed: bool,
-    ) -> Response {
-        // Get wallet for signer, or auto-register if signer is Address20 (EVM wallet)
-        let wallet = match self.get_wallet_for_signer(&signer).await {
-            Some(w) => w,
-            None => {
-                // Signer not mapped - try to derive wallet from Address20
-                match &signer {
-                    AccountOwner::Address20(addr) => {
-                        // Auto-bind this signer to wallet immediately
-                        self.state.signer_to_wallet.insert(&signer, *addr).unwrap();
-                        *addr
-                    },
-                    _ => return Response::Error { 
-                        message: "Player not registered. Call registerPlayer first.".to_string() 
-                    },
-                }
-            }
+    // ===== Helper: Store a ghost, replacing the player's previous one =====
+    async fn store_ghost(&mut self, run: &GameRun, data: Vec<u8>) {
+        let (run_id, tournament_id, wallet) = (run.id, run.tournament_id, run.wallet_address);
+        let key = (tournament_id, wallet);
+        let mut tp = match self.state.tournament_players.get(&key).await.ok().flatten().map(Versioned::into_inner) {
+            Some(tp) => tp,
+            None => return,
         };
 
-        // Get or create player to get username
-        let player = self.get_or_create_player(signer.clone(), wallet, "").await;
-        let username = player.username.clone();
+        let mut index = self.state.tournament_ghosts.get(&tournament_id).await.ok().flatten()
+            .unwrap_or_default();
+        if let Some(previous) = tp.ghost_run_id {
+            let _ = self.state.ghosts.remove(&previous);
+            index.retain(|id| *id != previous);
+        }
 
-        // ALWAYS send message to hub chain - NO branching logic
-        // Even if we ARE on the hub chain, we send a message to ourselves
-        // This ensures ALL state mutations go through execute_message
-        let hub_chain = self.runtime.application_creator_chain_id();
-        
-        let message = Message::ApplyRun {
-            wallet_address: wallet,
-            username,
+        let ghost = Ghost {
+            run_id,
             tournament_id,
-            time_ms,
-            score,
-            coins,
-            deaths,
-            completed,
+            wallet_address: wallet,
+            time_ms: run.time_ms,
+            ranked_time_ms: run.ranked_time_ms,
+            data,
+            stored_at: self.runtime.system_time(),
         };
-        
-        // Send message to hub chain
-        self.runtime.send_message(hub_chain, message);
-        
-        // Return success - actual state update happens in execute_message
-        Response::Ok
+        let _ = self.state.ghosts.insert(&run_id, ghost);
+        index.push(run_id);
+        let _ = self.state.tournament_ghosts.insert(&tournament_id, index);
+
+        tp.ghost_run_id = Some(run_id);
+        let _ = self.state.tournament_players.insert(&key, Versioned::new(tp));
     }
 
-    // NOTE: apply_run_on_hub has been DELETED
-    // NOTE: update_leaderboard has been DELETED
-    // ALL state mutations now happen ONLY inside execute_message
+    // ===== Helper: Drop ghosts of players pushed out of the top N =====
+    async fn prune_ghosts(&mut self, tournament_id: u64, leaderboard: &[LeaderboardEntry]) {
+        let index = self.state.tournament_ghosts.get(&tournament_id).await.ok().flatten()
+            .unwrap_or_default();
 
-    // ===== Create Tournament =====
-    async fn create_tournament(
-        &mut self,
-        _creator: AccountOwner,
-        title: String,
-        description: String,
-        maze_seed: String,
-        difficulty: Difficulty,
-        duration_days: u64,
-        xp_reward_pool: u64,
-    ) -> Response {
-        let now = self.runtime.system_time();
-        
-        // Calculate end time (duration_days * 24 * 60 * 60 * 1_000_000 microseconds)
-        let duration_micros = duration_days * 24 * 60 * 60 * 1_000_000;
-        let end_time = linera_sdk::linera_base_types::Timestamp::from(
-            now.micros() + duration_micros
-        );
+        let mut kept = Vec::new();
+        for run_id in index {
+            let ghost = match self.state.ghosts.get(&run_id).await.ok().flatten() {
+                Some(g) => g,
+                None => continue,
+            };
+            let in_top_n = leaderboard.iter()
+                .take(GHOST_LEADERBOARD_SLOTS as usize)
+                .any(|e| e.wallet_address == ghost.wallet_address);
+            if in_top_n {
+                kept.push(run_id);
+                continue;
+            }
 
-        let id = *self.state.next_tournament_id.get();
-        self.state.next_tournament_id.set(id + 1);
+            let _ = self.state.ghosts.remove(&run_id);
+            let key = (tournament_id, ghost.wallet_address);
+            if let Some(mut tp) = self.state.tournament_players.get(&key).await.ok().flatten().map(Versioned::into_inner) {
+                tp.ghost_run_id = None;
+                let _ = self.state.tournament_players.insert(&key, Versioned::new(tp));
+            }
+        }
+        let _ = self.state.tournament_ghosts.insert(&tournament_id, kept);
+    }
 
-        let tournament = Tournament {
-            id,
-            title,
-            description,
-            maze_seed: maze_seed.clone(),
-            difficulty,
-            start_time: now,
-            end_time,
-            status: TournamentStatus::Active,
-            participant_count: 0,
-            total_runs: 0,
-            xp_reward_pool,
-            created_at: now,
-        };
+    // ===== Helper: Credit run XP to a clan and its member =====
+    async fn credit_clan_xp(&mut self, clan_id: u64, wallet: [u8; 20], xp: u64) {
+        if let Some(mut clan) = self.state.clans.get(&clan_id).await.ok().flatten() {
+            clan.total_xp += xp;
+            let _ = self.state.clans.insert(&clan_id, clan);
+        }
+        if let Some(mut members) = self.state.clan_members.get(&clan_id).await.ok().flatten() {
+            for member in members.iter_mut().filter(|m| m.wallet_address == wallet) {
+                member.xp_contributed += xp;
+            }
+            let _ = self.state.clan_members.insert(&clan_id, members);
+        }
+    }
 
-        self.state.tournaments.insert(&id, tournament).unwrap();
-        self.state.leaderboards.insert(&id, Vec::new()).unwrap();
-        self.state.active_tournament_id.set(Some(id));
+    // ===== Helper: Take back clan XP credited by a cancelled tournament =====
+    async fn debit_clan_xp(&mut self, clan_id: u64, wallet: [u8; 20], xp: u64) {
+        if let Some(mut clan) = self.state.clans.get(&clan_id).await.ok().flatten() {
+            clan.total_xp = clan.total_xp.saturating_sub(xp);
+            let _ = self.state.clans.insert(&clan_id, clan);
+        }
+        if let Some(mut members) = self.state.clan_members.get(&clan_id).await.ok().flatten() {
+            for member in members.iter_mut().filter(|m| m.wallet_address == wallet) {
+                member.xp_contributed = member.xp_contributed.saturating_sub(xp);
+            }
+            let _ = self.state.clan_members.insert(&clan_id, members);
+        }
+    }
 
-        Response::TournamentCreated {
-            id,
-            maze_seed,
-            end_time,
+    // ===== Helper: Recompute a clan's entry on every active team board =====
+    // Roster changes move member scores between clans; disbanded clans drop off
+    async fn refresh_team_boards(&mut self, clan_id: u64) {
+        let mut team_tournaments = self.state.team_tournaments.get().clone();
+        let mut active = Vec::with_capacity(team_tournaments.len());
+        for id in &team_tournaments {
+            if let Some(tournament) = self.state.tournaments.get(id).await.ok().flatten().map(Versioned::into_inner) {
+                if tournament.status == TournamentStatus::Active {
+                    active.push(tournament);
+                }
+            }
+        }
+        team_tournaments.retain(|id| active.iter().any(|t| t.id == *id));
+        self.state.team_tournaments.set(team_tournaments);
+
+        for tournament in &active {
+            self.update_team_leaderboard(tournament, clan_id).await;
         }
     }
 
-    // ===== End Tournament (Finalize) =====
-    // STRICT ENFORCEMENT: Can only be called when now >= end_time
-    // This ensures the tournament runs for the full duration
-    // Anyone can call this (not admin-only) - the contract enforces timing
-    async fn end_tournament(
-        &mut self,
-        _caller: AccountOwner,
-        tournament_id: u64,
-    ) -> Response {
-        let mut tournament = match self.state.tournaments.get(&tournament_id).await.ok().flatten() {
-            Some(t) => t,
-            None => return Response::Error { message: "Tournament not found".to_string() },
-        };
+    // ===== Helper: Recompute a clan's entry on a team leaderboard =====
+    // Uses the clan's current members' best scores in this tournament
+    async fn update_team_leaderboard(&mut self, tournament: &Tournament, clan_id: u64) {
+        let mut board = self.state.team_leaderboards.get(&tournament.id).await.ok().flatten()
+            .unwrap_or_default();
+        board.retain(|e| e.clan_id != clan_id);
+
+        // A disbanded clan, or one with no scoring members left, is only removed
+        if let Some(clan) = self.state.clans.get(&clan_id).await.ok().flatten() {
+            let members = self.state.clan_members.get(&clan_id).await.ok().flatten()
+                .unwrap_or_default();
 
-        if tournament.status == TournamentStatus::Ended {
-            return Response::Error { message: "Tournament already ended".to_string() };
+            let mut member_scores = Vec::new();
+            for member in &members {
+                let key = (tournament.id, member.wallet_address);
+                if let Some(tp) = self.state.tournament_players.get(&key).await.ok().flatten().map(Versioned::into_inner) {
+                    member_scores.push(tp.best_score);
+                }
+            }
+            let (score, contributing_members) = tournament.team_mode
+                .team_score(member_scores, tournament.team_best_n);
+            if contributing_members > 0 {
+                board.push(TeamLeaderboardEntry {
+                    rank: 0,
+                    clan_id,
+                    clan_name: clan.name,
+                    clan_tag: clan.tag,
+                    score,
+                    contributing_members,
+                });
+            }
         }
 
-        // STRICT TIME ENFORCEMENT: Tournament must have reached end_time
-        let now = self.runtime.system_time();
-        if now < tournament.end_time {
-            return Response::Error { 
+        // Sort by score (descending - higher is better)
+        board.sort_by_key(|e| std::cmp::Reverse(e.score));
+        for (i, entry) in board.iter_mut().enumerate() {
+            entry.rank = (i + 1) as u32;
+        }
+        let _ = self.state.team_leaderboards.insert(&tournament.id, board);
+    }
+
+    // ===== Create Clan =====
+    async fn create_clan(&mut self, signer: AccountOwner, name: String, tag: String) -> Response {
+        let tag_len = tag.trim().chars().count();
+        if name.trim().is_empty() || !(MIN_CLAN_TAG_LEN..=MAX_CLAN_TAG_LEN).contains(&tag_len) {
+            return Response::Error {
                 message: format!(
-                    "Tournament cannot be finalized yet. Ends at timestamp {}",
-                    tournament.end_time.micros()
-                )
+                    "Clan needs a name and a {}-{} character tag",
+                    MIN_CLAN_TAG_LEN, MAX_CLAN_TAG_LEN
+                ),
             };
         }
 
-        // Mark as ended
-        tournament.status = TournamentStatus::Ended;
-        self.state.tournaments.insert(&tournament_id, tournament.clone()).unwrap();
+        self.clan_action(signer, |wallet, username| Message::CreateClan {
+            wallet_address: wallet,
+            username,
+            name,
+            tag,
+        }).await
+    }
 
-        // Clear active tournament if this was it
-        if self.state.active_tournament_id.get() == &Some(tournament_id) {
-            self.state.active_tournament_id.set(None);
-        }
+    // ===== Clan Action =====
+    // Like submit_run: clan membership lives on the hub, so every action is a message
+    async fn clan_action(
+        &mut self,
+        signer: AccountOwner,
+        message: impl FnOnce([u8; 20], String) -> Message,
+    ) -> Response {
+        let (wallet, username) = match self.resolve_player(signer).await {
+            Ok(p) => p,
+            Err(response) => return response,
+        };
 
-        // Get leaderboard and create rewards for top 5
-        let leaderboard = self.state.leaderboards.get(&tournament_id).await.ok().flatten()
+        self.send_to_hub(message(wallet, username));
+        Response::Ok
+    }
+
+    // ===== Helper: Add duel to a player's duel index =====
+    async fn index_duel(&mut self, wallet: [u8; 20], duel_id: u64) {
+        let mut ids = self.state.player_duels.get(&wallet).await.ok().flatten()
             .unwrap_or_default();
+        if !ids.contains(&duel_id) {
+            ids.push(duel_id);
+            let _ = self.state.player_duels.insert(&wallet, ids);
+        }
+    }
 
-        // Reward distribution: 1st=40%, 2nd=25%, 3rd=15%, 4th=12%, 5th=8%
-        let reward_percentages = [40u64, 25, 15, 12, 8];
-        let mut winner_count = 0u32;
+    // ===== Helper: Settle duel and feed result into player stats =====
+    // Caller persists the duel
+    async fn settle_duel(&mut self, duel: &mut Duel) {
+        let opponent = match duel.opponent {
+            Some(o) if duel.challenger_run.is_some() || duel.opponent_run.is_some() => o,
+            _ => {
+                // Nobody played (or nobody accepted the open lobby)
+                duel.status = DuelStatus::Expired;
+                if duel.opponent.is_none() {
+                    let mut open = self.state.open_duels.get().clone();
+                    open.retain(|id| *id != duel.id);
+                    self.state.open_duels.set(open);
+                }
+                return;
+            }
+        };
 
-        for (i, entry) in leaderboard.iter().take(5).enumerate() {
-            let xp_amount = (tournament.xp_reward_pool * reward_percentages[i]) / 100;
-            
-            let reward = TournamentReward {
-                tournament_id,
-                wallet_address: entry.wallet_address,
-                rank: entry.rank,
-                xp_amount,
-                claimed: false,
-            };
+        duel.status = DuelStatus::Completed;
+        duel.winner = duel.decide_winner();
 
-            let key = (tournament_id, entry.wallet_address);
-            self.state.rewards.insert(&key, reward).unwrap();
-            
-            // Update player's tournaments_won for 1st place
-            if i == 0 {
-                if let Some(mut player) = self.state.players.get(&entry.wallet_address).await.ok().flatten() {
-                    player.tournaments_won += 1;
-                    self.state.players.insert(&entry.wallet_address, player).unwrap();
+        for wallet in [duel.challenger, opponent] {
+            if let Some(mut player) = self.state.players.get(&wallet).await.ok().flatten().map(Versioned::into_inner) {
+                player.duels_played += 1;
+                if duel.winner == Some(wallet) {
+                    player.duels_won += 1;
                 }
+                let _ = self.state.players.insert(&wallet, Versioned::new(player));
             }
-            
-            winner_count += 1;
-        }
-
-        Response::TournamentEnded {
-            id: tournament_id,
-            winner_count,
         }
     }
 
-    // ===== Claim Reward =====
-    async fn claim_reward(
+    // ===== Create Duel =====
+    // Like submit_run: player actions ALWAYS go to the hub via message
+    async fn create_duel(
         &mut self,
         signer: AccountOwner,
-        tournament_id: u64,
+        opponent: Option<[u8; 20]>,
+        difficulty: Difficulty,
+        duration_hours: u64,
     ) -> Response {
-        // Get wallet for signer
-        let wallet = match self.get_wallet_for_signer(&signer).await {
-            Some(w) => w,
-            None => return Response::Error { message: "Player not registered".to_string() },
+        let (wallet, username) = match self.resolve_player(signer).await {
+            Ok(p) => p,
+            Err(response) => return response,
         };
+        if opponent == Some(wallet) {
+            return Response::Error { message: "Cannot challenge yourself".to_string() };
+        }
 
-        let key = (tournament_id, wallet);
-        let mut reward = match self.state.rewards.get(&key).await.ok().flatten() {
-            Some(r) => r,
-            None => return Response::Error { message: "No reward found for this tournament".to_string() },
+        self.send_to_hub(Message::OpenDuel {
+            challenger: wallet,
+            username,
+            opponent,
+            difficulty,
+            duration_hours,
+        });
+
+        Response::Ok
+    }
+
+    // ===== Accept Duel =====
+    async fn accept_duel(&mut self, signer: AccountOwner, duel_id: u64) -> Response {
+        let (wallet, username) = match self.resolve_player(signer).await {
+            Ok(p) => p,
+            Err(response) => return response,
         };
 
-        if reward.claimed {
-            return Response::Error { message: "Reward already claimed".to_string() };
-        }
+        self.send_to_hub(Message::AcceptDuel {
+            wallet_address: wallet,
+            username,
+            duel_id,
+        });
 
-        // Mark as claimed
-        reward.claimed = true;
-        self.state.rewards.insert(&key, reward.clone()).unwrap();
+        Response::Ok
+    }
 
-        // Add XP to player
-        if let Some(mut player) = self.state.players.get(&wallet).await.ok().flatten() {
-            player.total_xp += reward.xp_amount;
-            self.state.players.insert(&wallet, player).unwrap();
+    // ===== Submit Duel Run =====
+    async fn submit_duel_run(&mut self, signer: AccountOwner, run: DuelRunSubmission) -> Response {
+        let (wallet, _) = match self.resolve_player(signer).await {
+            Ok(p) => p,
+            Err(response) => return response,
+        };
+
+        self.send_to_hub(Message::ApplyDuelRun { wallet_address: wallet, run });
+
+        Response::Ok
+    }
+
+    // ===== Resolve Duel =====
+    // Anyone can call once the deadline has passed
+    async fn resolve_duel(&mut self, duel_id: u64) -> Response {
+        let mut duel = match self.state.duels.get(&duel_id).await.ok().flatten() {
+            Some(d) => d,
+            None => return Response::Error { message: "Duel not found".to_string() },
+        };
+
+        if matches!(duel.status, DuelStatus::Completed | DuelStatus::Expired) {
+            return Response::Error { message: "Duel already resolved".to_string() };
         }
 
-        Response::RewardClaimed {
-            tournament_id,
-            xp_amount: reward.xp_amount,
+        let now = self.runtime.system_time();
+        if now < duel.deadline {
+            return Response::Error {
+                message: format!(
+                    "Duel cannot be resolved yet. Deadline is timestamp {}",
+                    duel.deadline.micros()
+                )
+            };
+        }
+
+        self.settle_duel(&mut duel).await;
+        self.state.duels.insert(&duel_id, duel.clone()).unwrap();
+
+        Response::DuelResolved {
+            id: duel_id,
+            status: duel.status,
+            winner: duel.winner,
         }
     }
-    
+
     // ===== Bootstrap Tournament (Workaround for instantiate not persisting) =====
-    // Creates tournament #1 if it doesn't exist
+    // Seeds the default template and spawns its first instance if tournament #1 doesn't exist
     // This is idempotent - calling multiple times has no effect
     async fn bootstrap_tournament(&mut self) -> Response {
         // Check if tournament #1 already exists in MapView
         if let Ok(Some(existing)) = self.state.tournaments.get(&1u64).await {
-            // Tournament exists in MapView - ensure RegisterView is also set
-            // This is CRITICAL: execute_message reads from RegisterView, not MapView!
-            self.state.active_tournament.set(Some(existing.clone()));
-            self.state.active_tournament_id.set(Some(1));
+            let existing = existing.into_inner();
+            if self.state.active_tournament_id.get().is_none() {
+                self.state.active_tournament_id.set(Some(1));
+            }
             
             return Response::TournamentBootstrapped {
                 id: 1,
@@ -663,54 +3419,61 @@ impl LabyrinthTournamentContract {
             };
         }
 
-        // Tournament doesn't exist - create it
-        let now = self.runtime.system_time();
-        
-        // Tournament duration: 15 days in microseconds
-        const DURATION_DAYS: u64 = 15;
-        let duration_micros = DURATION_DAYS * 24 * 60 * 60 * 1_000_000;
-        let end_time = linera_sdk::linera_base_types::Timestamp::from(
-            now.micros() + duration_micros
-        );
-        
-        // Deterministic seed from current timestamp
-        let maze_seed = format!("labyrinth_legends_{}", now.micros() / 1_000_000);
-        
-        let tournament = Tournament {
-            id: 1,
-            title: "Labyrinth Legends Championship".to_string(),
-            description: "15-day tournament - Navigate the maze faster than anyone else!".to_string(),
-            maze_seed,
-            difficulty: Difficulty::Medium,
-            start_time: now,
-            end_time,
-            status: TournamentStatus::Active,
-            participant_count: 0,
-            total_runs: 0,
-            xp_reward_pool: 10000,
-            created_at: now,
-        };
-        
-        // Store tournament in BOTH places for consistency:
-        // 1. MapView for historical lookup and persistence
-        // 2. RegisterView for quick access and state mutations in execute_message
-        self.state.tournaments.insert(&1u64, tournament.clone()).unwrap();
-        self.state.active_tournament.set(Some(tournament)); // CRITICAL: Set RegisterView!
-        self.state.leaderboards.insert(&1u64, Vec::new()).unwrap();
-        self.state.active_tournament_id.set(Some(1));
-        
         // Initialize counters if not set
-        if *self.state.next_tournament_id.get() < 2 {
-            self.state.next_tournament_id.set(2);
-        }
         if *self.state.next_run_id.get() < 1 {
             self.state.next_run_id.set(1);
         }
-        
-        Response::TournamentBootstrapped {
-            id: 1,
-            end_time,
-            already_existed: false,
+        if *self.state.next_template_id.get() < 1 {
+            self.seed_default_template();
+        }
+        self.rotate_tournaments().await;
+
+        let featured = match *self.state.active_tournament_id.get() {
+            Some(id) => self.state.tournaments.get(&id).await.ok().flatten().map(Versioned::into_inner),
+            None => None,
+        };
+        match featured {
+            Some(tournament) => Response::TournamentBootstrapped {
+                id: tournament.id,
+                end_time: tournament.end_time,
+                already_existed: false,
+            },
+            None => Response::Error { message: "No template spawned a tournament".to_string() },
+        }
+    }
+}
+
+/// Re-inserts up to `limit` entries of `map` whose BCS key sorts after `after`
+/// (entries are visited in key byte order, so inserts and removals between batches
+/// neither skip nor repeat entries). Values decode through `Versioned`, upgrading
+/// older layouts, and are written back tagged in the current layout.
+/// Returns the number of entries rewritten and the key of the last one
+/// MapView cannot start iterating at a key, so every batch rescans from the first key
+async fn rewrite_entries<I, T>(
+    map: &mut MapView<I, Versioned<T>>,
+    after: Option<&[u8]>,
+    limit: u64,
+) -> Result<(u64, Option<Vec<u8>>), ViewError>
+where
+    I: Serialize + DeserializeOwned + Send,
+    T: VersionedRecord + Clone + Send + Sync + 'static,
+{
+    let mut batch = Vec::new();
+    map.for_each_index_while(|index| {
+        let key = bcs::to_bytes(&index)?;
+        if after.is_none_or(|after| key.as_slice() > after) {
+            batch.push((index, key));
+        }
+        Ok((batch.len() as u64) < limit)
+    }).await?;
+
+    let count = batch.len() as u64;
+    let mut last_key = None;
+    for (index, key) in batch {
+        if let Some(value) = map.get(&index).await? {
+            map.insert(&index, value)?;
         }
+        last_key = Some(key);
     }
+    Ok((count, last_key))
 }
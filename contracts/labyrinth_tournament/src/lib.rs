@@ -7,7 +7,10 @@
 //! 3. All scoring/XP/leaderboard logic is on-chain
 //! 4. Backend is optional cache/indexer only
 
-use linera_sdk::linera_base_types::Timestamp;
+pub mod maze;
+pub mod schema;
+
+use linera_sdk::linera_base_types::{ChainId, Timestamp};
 
 // Re-export AccountOwner for service.rs
 pub use linera_sdk::linera_base_types::AccountOwner;
@@ -22,6 +25,101 @@ use serde::{Deserialize, Serialize};
 pub enum TournamentStatus {
     Active,    // Currently accepting submissions
     Ended,     // Completed, leaderboard frozen
+    Cancelled, // Aborted by the organizer, no rewards
+}
+
+/// Where a submitted run stands, as seen from the submitting chain
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum ReceiptStatus {
+    Pending,   // Sent, no answer from the hub yet
+    Confirmed, // Hub applied the run
+    Rejected,  // Hub refused the run (see reason)
+    Bounced,   // Hub chain rejected the message itself
+}
+
+/// How often a tournament template spawns a new instance
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum Recurrence {
+    Daily,
+    Weekly,
+    SemiMonthly, // Every 15 days
+    Monthly,   // Every 30 days
+}
+
+impl Recurrence {
+    pub fn period_micros(&self) -> u64 {
+        let days = match self {
+            Recurrence::Daily => 1,
+            Recurrence::Weekly => 7,
+            Recurrence::SemiMonthly => 15,
+            Recurrence::Monthly => 30,
+        };
+        days * 24 * 60 * 60 * 1_000_000
+    }
+}
+
+/// Tournament field changed by UpdateTournament
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum AmendedField {
+    Title,
+    Description,
+    Difficulty,
+    MazeConfig,
+    EndTime,
+    XpRewardPool,
+}
+
+/// What happens to XP earned in a cancelled tournament
+/// Achievements unlocked by the tournament's runs are kept either way
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum CancelXpPolicy {
+    Keep,      // Players keep the XP their runs earned
+    Revert,    // Run XP is taken back from every participant and their clans
+}
+
+/// Season status lifecycle
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum SeasonStatus {
+    Active,    // Tournaments created now join this season
+    Ended,     // Standings frozen, rewards available
+}
+
+/// Duel status lifecycle
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum DuelStatus {
+    Open,       // Waiting for the opponent to accept
+    Accepted,   // Both players may submit their run
+    Completed,  // Winner decided (or draw)
+    Expired,    // Deadline passed without any run
+}
+
+/// Clan membership roles
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum ClanRole {
+    Leader,     // Can promote/demote and invite
+    Officer,    // Can invite
+    Member,
+}
+
+/// How a tournament is scored for clans
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum TeamMode {
+    Individual, // No team leaderboard
+    TeamSum,    // Clan score = sum of all members' best scores
+    TeamBestN,  // Clan score = sum of the best `team_best_n` members' best scores
+}
+
+/// How maze seeds are assigned to runs
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum SeedMode {
+    Shared,     // Every run plays the tournament's maze_seed
+    PerAttempt, // Each run gets a seed derived from (maze_seed, run ticket entropy)
+}
+
+/// Maze generation algorithm (must be implemented by every client)
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum MazeAlgorithm {
+    RecursiveBacktracker, // game-engine/src/maze.js (mirrored in maze.rs)
 }
 
 /// Difficulty levels (affects XP calculation)
@@ -42,6 +140,28 @@ impl Difficulty {
             Difficulty::Nightmare => 150,
         }
     }
+
+    /// Default maze for this difficulty
+    /// Size matches getDifficultySettings in the game engine; per-stage sizes and
+    /// coins match DIFFICULTY_CONFIG in the frontend's gameConfig.ts
+    pub fn default_maze_config(&self) -> MazeConfig {
+        let (size, trap_density_percent, stage_sizes, stage_coins): (u32, u32, &[u32], &[u32]) = match self {
+            Difficulty::Easy => (6, 0, &[5, 6, 7], &[5, 7, 10]),
+            Difficulty::Medium => (10, 5, &[8, 10, 12, 14], &[8, 10, 12, 15]),
+            Difficulty::Hard => (15, 10, &[12, 14, 16, 18, 20], &[10, 12, 14, 16, 18]),
+            Difficulty::Nightmare => (20, 15, &[16, 18, 20, 22, 25], &[12, 14, 16, 18, 20]),
+        };
+        MazeConfig {
+            width: size,
+            height: size,
+            stages: stage_sizes.len() as u32,
+            algorithm: MazeAlgorithm::RecursiveBacktracker,
+            trap_density_percent,
+            stage_sizes: stage_sizes.to_vec(),
+            stage_coins: stage_coins.to_vec(),
+            time_limit_ms: None,
+        }
+    }
 }
 
 // ============================================
@@ -50,13 +170,13 @@ impl Difficulty {
 
 /// Tournament configuration and state
 /// A tournament runs for a fixed period (e.g., 15 days)
-/// All players compete on the same maze seed
+/// All players compete on the same maze seed (or per-attempt seeds derived from it)
 #[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
 pub struct Tournament {
     pub id: u64,
     pub title: String,
     pub description: String,
-    pub maze_seed: String,              // Same maze for all players
+    pub maze_seed: String,              // Same maze for all players (empty until the seed is revealed)
     pub difficulty: Difficulty,
     pub start_time: Timestamp,
     pub end_time: Timestamp,
@@ -65,6 +185,153 @@ pub struct Tournament {
     pub total_runs: u64,
     pub xp_reward_pool: u64,            // XP to distribute to top players
     pub created_at: Timestamp,
+    pub season_id: Option<u64>,         // Season this tournament counts towards
+    pub team_mode: TeamMode,
+    pub team_best_n: u32,               // Members counted in TeamBestN mode
+    pub commit_reveal_required: bool,   // Runs must reveal a prior CommitRun
+    pub seed_mode: SeedMode,
+    pub reference_path_length: u32,     // Shortest path of maze_seed - PerAttempt times are scaled to it
+    pub seed_commitment: Option<[u8; 32]>, // Organizer's sha3_256(secret), fixed at creation
+    pub seed_entropy: [u8; 32],         // Chain entropy captured at creation
+    pub seed_secret: Option<Vec<u8>>,   // Organizer secret, published on reveal
+    pub seed_revealed: bool,            // Runs are accepted only once the seed is known
+    pub maze_config: MazeConfig,        // Everything a client needs besides the seed to render the maze
+    pub organizer: Option<AccountOwner>, // Signer that created it (None for default/template tournaments)
+    pub cancelled_at: Option<Timestamp>,
+    pub reward_percentages: Vec<u64>,   // Share of xp_reward_pool per final rank (index 0 = 1st)
+    pub template_id: Option<u64>,       // Template this tournament was spawned from
+}
+
+/// Recurring tournament definition; RotateTournaments spawns its instances
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct TournamentTemplate {
+    pub id: u64,
+    pub title_pattern: String,          // "{n}" is replaced by the instance number
+    pub description: String,
+    pub difficulty: Difficulty,
+    pub maze_config: MazeConfig,
+    pub duration_hours: u64,
+    pub xp_reward_pool: u64,
+    pub reward_percentages: Vec<u64>,
+    pub recurrence: Recurrence,
+    pub seed_mode: SeedMode,
+    pub team_mode: TeamMode,
+    pub team_best_n: u32,
+    pub active: bool,                   // Paused templates spawn nothing
+    pub instances_created: u32,
+    pub current_tournament_id: Option<u64>,
+    pub next_start: Timestamp,          // Earliest time the next instance may start
+    pub created_at: Timestamp,
+}
+
+/// Template definition as submitted by CreateTemplate
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::InputObject)]
+pub struct TemplateInput {
+    pub title_pattern: String,
+    pub description: String,
+    pub difficulty: Difficulty,
+    pub maze_config: Option<MazeConfig>, // Defaults to the difficulty's standard maze
+    pub duration_hours: u64,            // At most one recurrence period
+    pub xp_reward_pool: u64,
+    pub reward_percentages: Vec<u64>,   // Empty = DEFAULT_REWARD_PERCENTAGES
+    pub recurrence: Recurrence,
+    pub seed_mode: SeedMode,
+    pub team_mode: TeamMode,
+    pub team_best_n: u32,
+}
+
+impl TemplateInput {
+    /// The recurring championship every deployment starts with
+    pub fn default_championship() -> Self {
+        TemplateInput {
+            title_pattern: "Labyrinth Legends Championship #{n}".to_string(),
+            description: "15-day tournament - Navigate the maze faster than anyone else!".to_string(),
+            difficulty: Difficulty::Medium,
+            maze_config: None,
+            duration_hours: 15 * 24,
+            xp_reward_pool: 10000, // 10,000 XP pool for top players
+            reward_percentages: Vec::new(),
+            recurrence: Recurrence::SemiMonthly,
+            seed_mode: SeedMode::Shared,
+            team_mode: TeamMode::Individual,
+            team_best_n: 0,
+        }
+    }
+}
+
+/// Tournament definition as given to the service's createTournament
+/// seed_commitment_hex = sha3_256(secret); unset options take the defaults
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::InputObject)]
+pub struct TournamentInput {
+    pub title: String,
+    pub description: String,
+    pub seed_commitment_hex: Option<String>,
+    pub difficulty: Difficulty,
+    pub duration_days: u64,
+    pub xp_reward_pool: u64,
+    pub team_mode: Option<TeamMode>,
+    pub team_best_n: Option<u32>,
+    pub commit_reveal_required: Option<bool>,
+    pub seed_mode: Option<SeedMode>,
+    pub maze_config: Option<MazeConfig>,      // Defaults to the difficulty's standard maze
+    pub reward_percentages: Option<Vec<u64>>, // Defaults to DEFAULT_REWARD_PERCENTAGES
+}
+
+/// A new tournament's definition, from CreateTournament or a template instance
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TournamentParams {
+    pub title: String,
+    pub description: String,
+    pub seed_commitment: Option<[u8; 32]>,
+    pub difficulty: Difficulty,
+    pub duration_micros: u64,
+    pub xp_reward_pool: u64,
+    pub team_mode: TeamMode,
+    pub team_best_n: u32,
+    pub commit_reveal_required: bool,
+    pub seed_mode: SeedMode,
+    pub maze_config: MazeConfig,
+    pub reward_percentages: Vec<u64>,
+    pub template_id: Option<u64>,
+}
+
+/// Requested tournament changes (unset fields are left alone)
+/// Title, description, difficulty and maze only change before the first run
+/// (a difficulty without a maze resets the maze to that difficulty's default);
+/// end_time can only be extended before it passes and xp_reward_pool only increased
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::InputObject)]
+pub struct TournamentUpdate {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub difficulty: Option<Difficulty>,
+    pub maze_config: Option<MazeConfig>,
+    pub extend_hours: Option<u64>,
+    pub add_xp_reward_pool: Option<u64>,
+}
+
+/// Audit record of one changed tournament field
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct TournamentAmendment {
+    pub tournament_id: u64,
+    pub field: AmendedField,
+    pub old_value: String,
+    pub new_value: String,
+    pub amended_by: AccountOwner,
+    pub amended_at: Timestamp,
+}
+
+/// Maze parameters shared by every client of a tournament
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject, async_graphql::InputObject)]
+#[graphql(input_name = "MazeConfigInput")]
+pub struct MazeConfig {
+    pub width: u32,                     // Cells of the seed's reference maze (path-length normalization)
+    pub height: u32,                    // Cells
+    pub stages: u32,                    // Stages per run (splits may not exceed it)
+    pub algorithm: MazeAlgorithm,
+    pub trap_density_percent: u32,      // Share of cells holding a trap
+    pub stage_sizes: Vec<u32>,          // Side length of each stage's maze (index 0 = stage 1)
+    pub stage_coins: Vec<u32>,          // Coins placed in each stage (index 0 = stage 1)
+    pub time_limit_ms: Option<u64>,     // Runs slower than this are rejected
 }
 
 /// Player stats within a specific tournament
@@ -72,12 +339,15 @@ pub struct Tournament {
 pub struct TournamentPlayer {
     pub wallet_address: [u8; 20],
     pub username: String,
-    pub best_time_ms: u64,              // Best completion time (lower = better)
+    pub best_time_ms: u64,              // Best completion time (lower = better, normalized in PerAttempt mode)
     pub best_score: u64,                // Best score (higher = better)
     pub total_runs: u32,
     pub total_xp_earned: u64,
     pub last_run_at: Timestamp,
     pub joined_at: Timestamp,
+    pub best_splits: Vec<u64>,          // Best time per stage (index 0 = stage 1, u64::MAX = not cleared)
+    pub sum_of_best_ms: Option<u64>,    // Sum of best_splits once every stage has been cleared
+    pub ghost_run_id: Option<u64>,      // Run whose ghost replay is stored (personal best in top N)
 }
 
 /// Global player profile (aggregates all tournament stats)
@@ -92,6 +362,12 @@ pub struct Player {
     pub best_time_ms: Option<u64>,      // Best time across all tournaments
     pub registered_at: Timestamp,
     pub last_active: Timestamp,
+    pub rating: u32,                    // Glicko skill rating (starts at 1500)
+    pub rating_deviation: u32,          // Rating uncertainty (starts at 350)
+    pub rated_tournaments: u32,
+    pub rating_provisional: bool,       // True while rating_deviation is high
+    pub duels_played: u32,
+    pub duels_won: u32,
 }
 
 /// Single game run record
@@ -108,10 +384,179 @@ pub struct GameRun {
     pub completed: bool,
     pub xp_earned: u64,
     pub created_at: Timestamp,
+    pub splits: Vec<StageSplit>,        // Per-stage splits (empty for single-stage clients)
+    pub verified: bool,                 // Revealed a matching commitment (commit-reveal flow)
+    pub ticket_id: u64,                 // Run ticket consumed by this run
+    pub ticket_nonce: u64,
+    pub maze_seed: String,              // Seed actually played (derived in PerAttempt mode)
+    pub path_length: u32,               // Shortest path length of that maze
+    pub ranked_time_ms: u64,            // Time used for ranking (normalized in PerAttempt mode)
+    pub ticket_entropy: [u8; 32],       // Entropy of the consumed ticket (recomputes maze_seed)
 }
 
-/// Leaderboard entry (sorted by best_time_ms ascending)
+/// Run ticket issued by the hub when a run starts (StartRun)
+/// Each ticket admits exactly one SubmitRun and is removed once used or expired
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct RunTicket {
+    pub id: u64,
+    pub tournament_id: u64,
+    pub wallet_address: [u8; 20],
+    pub nonce: u64,                     // Chosen by the client, unique among the wallet's open tickets
+    pub issued_at: Timestamp,           // Hub chain time - bounds the claimed time_ms
+    pub entropy: [u8; 32],              // Chain entropy captured at issue
+    pub attempt_seed: String,           // Maze seed the run plays, unknown until the ticket exists
+}
+
+/// Run commitment recorded on the hub when a run starts
+/// The reveal must match `compute_run_commitment` for the run's maze seed
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct RunCommitment {
+    pub tournament_id: u64,
+    pub wallet_address: [u8; 20],
+    pub commitment: [u8; 32],
+    pub committed_at: Timestamp,        // Hub chain time - bounds the claimed time_ms
+}
+
+/// Delivery receipt for a SubmitRun, kept on the submitting chain
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct RunReceipt {
+    pub id: u64,
+    pub wallet_address: [u8; 20],
+    pub tournament_id: u64,
+    pub time_ms: u64,
+    pub ticket_nonce: u64,
+    pub status: ReceiptStatus,
+    pub run_id: Option<u64>,            // Hub run ID once confirmed
+    pub xp_earned: u64,
+    pub rank: Option<u32>,              // Leaderboard rank after the run (None outside the top 100)
+    pub reason: Option<String>,         // Why the hub rejected the run
+    pub submitted_at: Timestamp,
+    pub resolved_at: Option<Timestamp>,
+}
+
+/// Reveal for a committed run
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RunReveal {
+    pub moves: Vec<u8>,                 // Encoded input trace (format defined by the client)
+    pub salt: Vec<u8>,
+}
+
+/// One run as submitted by a player (SubmitRun, or an entry of SubmitRuns)
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RunSubmission {
+    pub tournament_id: u64,
+    pub time_ms: u64,
+    pub score: u64,
+    pub coins: u32,
+    pub deaths: u32,
+    pub completed: bool,
+    pub splits: Vec<StageSplit>,
+    pub ghost: Option<Vec<u8>>,
+    pub reveal: Option<RunReveal>,
+    pub ticket_nonce: u64,
+}
+
+impl RunSubmission {
+    /// Checks the submitting chain can make on its own (the hub re-checks everything)
+    pub fn precheck(&self) -> Result<(), String> {
+        validate_splits(&self.splits, self.time_ms, self.deaths, self.coins)?;
+        if self.ghost.as_ref().is_some_and(|g| g.len() > MAX_GHOST_BYTES) {
+            return Err(format!("Ghost trace exceeds {} bytes", MAX_GHOST_BYTES));
+        }
+        if self.reveal.as_ref().is_some_and(|r| r.moves.len() > MAX_REVEAL_BYTES) {
+            return Err(format!("Revealed moves exceed {} bytes", MAX_REVEAL_BYTES));
+        }
+        Ok(())
+    }
+}
+
+/// One run of a SubmitRuns batch, as given to the service (byte fields are hex)
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::InputObject)]
+pub struct RunSubmissionInput {
+    pub tournament_id: u64,
+    pub time_ms: u64,
+    pub score: u64,
+    pub coins: u32,
+    pub deaths: u32,
+    pub completed: bool,
+    pub splits: Option<Vec<StageSplit>>,
+    pub ghost_hex: Option<String>,
+    pub reveal_moves_hex: Option<String>,
+    pub reveal_salt_hex: Option<String>,
+    pub ticket_nonce: u64,
+}
+
+/// Hub's verdict on one submitted run
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct RunResult {
+    pub run_id: Option<u64>,            // Set when the run was applied
+    pub xp_earned: u64,
+    pub reason: Option<String>,         // Set when the run was rejected
+}
+
+impl RunResult {
+    pub fn accepted(run_id: u64, xp_earned: u64) -> Self {
+        RunResult { run_id: Some(run_id), xp_earned, reason: None }
+    }
+
+    pub fn rejected(reason: String) -> Self {
+        RunResult { run_id: None, xp_earned: 0, reason: Some(reason) }
+    }
+}
+
+/// Maximum runs in one SubmitRuns batch
+pub const MAX_BATCH_RUNS: usize = 50;
+
+/// Per-stage split within a multi-stage run
+/// Stages are numbered from 1; splits must add up to the run totals
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject, async_graphql::InputObject)]
+#[graphql(input_name = "StageSplitInput")]
+pub struct StageSplit {
+    pub stage: u32,
+    pub time_ms: u64,
+    pub deaths: u32,
+    pub coins: u32,
+}
+
+/// Ghost replay: compressed input/position trace of a run
+/// Only kept for personal bests ranked in the top GHOST_LEADERBOARD_SLOTS
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+#[graphql(complex)]
+pub struct Ghost {
+    pub run_id: u64,
+    pub tournament_id: u64,
+    pub wallet_address: [u8; 20],
+    pub time_ms: u64,
+    pub ranked_time_ms: u64,            // Leaderboard time of the run (normalized in PerAttempt mode)
+    #[graphql(skip)]
+    pub data: Vec<u8>,
+    pub stored_at: Timestamp,
+}
+
+#[async_graphql::ComplexObject]
+impl Ghost {
+    /// Trace bytes as hex (format is defined by the game client)
+    async fn data_hex(&self) -> String {
+        hex::encode(&self.data)
+    }
+
+    /// Trace size in bytes
+    async fn size(&self) -> u32 {
+        self.data.len() as u32
+    }
+}
+
+/// Split-time leaderboard entry (per stage or sum of best, sorted by time ascending)
 #[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct SplitLeaderboardEntry {
+    pub rank: u32,
+    pub wallet_address: [u8; 20],
+    pub username: String,
+    pub time_ms: u64,
+}
+
+/// Leaderboard entry (sorted by best_time_ms ascending)
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, async_graphql::SimpleObject)]
 pub struct LeaderboardEntry {
     pub rank: u32,
     pub wallet_address: [u8; 20],
@@ -122,6 +567,63 @@ pub struct LeaderboardEntry {
     pub total_xp: u64,
 }
 
+/// Top of the leaderboard highlighted in deltas (see LeaderboardChange::new_in_top)
+pub const LEADERBOARD_TOP_SLOTS: u32 = 10;
+
+/// Maximum chains following one tournament's leaderboard
+pub const MAX_LEADERBOARD_SUBSCRIBERS: usize = 200;
+
+/// A leaderboard row that changed, as pushed to subscribed chains
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct LeaderboardChange {
+    pub entry: LeaderboardEntry,
+    pub previous_rank: Option<u32>,     // None = new on the board
+    pub new_in_top: bool,               // Entered the top LEADERBOARD_TOP_SLOTS
+}
+
+/// Rows of `new` that differ from `old`, and wallets that dropped off the board
+pub fn leaderboard_changes(
+    old: &[LeaderboardEntry],
+    new: &[LeaderboardEntry],
+) -> (Vec<LeaderboardChange>, Vec<[u8; 20]>) {
+    let mut changes = Vec::new();
+    for entry in new {
+        let previous = old.iter().find(|e| e.wallet_address == entry.wallet_address);
+        if previous == Some(entry) {
+            continue;
+        }
+        let previous_rank = previous.map(|e| e.rank);
+        changes.push(LeaderboardChange {
+            new_in_top: entry.rank <= LEADERBOARD_TOP_SLOTS
+                && previous_rank.is_none_or(|rank| rank > LEADERBOARD_TOP_SLOTS),
+            entry: entry.clone(),
+            previous_rank,
+        });
+    }
+    let removed = old.iter()
+        .filter(|e| !new.iter().any(|n| n.wallet_address == e.wallet_address))
+        .map(|e| e.wallet_address)
+        .collect();
+    (changes, removed)
+}
+
+/// Apply pushed changes to a cached copy of a leaderboard
+pub fn apply_leaderboard_changes(
+    board: &mut Vec<LeaderboardEntry>,
+    changes: Vec<LeaderboardChange>,
+    removed: &[[u8; 20]],
+) {
+    board.retain(|e| !removed.contains(&e.wallet_address));
+    for change in changes {
+        match board.iter_mut().find(|e| e.wallet_address == change.entry.wallet_address) {
+            Some(entry) => *entry = change.entry,
+            None => board.push(change.entry),
+        }
+    }
+    board.sort_by_key(|e| e.rank);
+    board.truncate(100);
+}
+
 /// Tournament rewards for top players
 #[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
 pub struct TournamentReward {
@@ -132,94 +634,1204 @@ pub struct TournamentReward {
     pub claimed: bool,
 }
 
-// ============================================
-// XP CALCULATION
-// ============================================
+/// Season grouping several tournaments into one ranking
+/// Season points are awarded from final tournament placements
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct Season {
+    pub id: u64,
+    pub title: String,
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    pub status: SeasonStatus,
+    pub tournament_ids: Vec<u64>,       // Tournaments counting towards this season
+    pub xp_reward_pool: u64,            // XP to distribute to top players at season end
+    pub created_at: Timestamp,
+}
 
-impl Difficulty {
-    /// Calculate XP earned for a run
-    /// Formula: base_xp + completion_bonus + time_bonus - death_penalty
-    pub fn calculate_xp(&self, time_ms: u64, deaths: u32, completed: bool) -> u64 {
-        let base = self.base_xp();
-        
-        // Completion bonus: +100% of base if completed
-        let completion_bonus = if completed { base } else { 0 };
-        
-        // Time bonus: up to +100% of base for fast completion (under 2 min)
-        let time_secs = time_ms / 1000;
-        let time_bonus = if completed && time_secs < 120 {
-            (base * (120 - time_secs)) / 120
-        } else {
-            0
-        };
-        
-        // Death penalty: -10% per death, max 50%
-        let death_penalty = std::cmp::min(deaths as u64 * 10, 50);
-        let death_multiplier = 100 - death_penalty;
-        
-        // Final calculation
-        let raw_xp = (base + completion_bonus + time_bonus) * death_multiplier / 100;
-        std::cmp::max(10, raw_xp)  // Minimum 10 XP
-    }
+/// Season leaderboard entry (sorted by points descending)
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct SeasonStanding {
+    pub rank: u32,
+    pub wallet_address: [u8; 20],
+    pub username: String,
+    pub points: u64,                    // Includes carried_points
+    pub carried_points: u64,            // Soft-reset carry-over from the previous season
+    pub tournaments_scored: u32,
 }
 
-// ============================================
-// CROSS-CHAIN MESSAGES
-// ============================================
+/// Season rewards for top players
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct SeasonReward {
+    pub season_id: u64,
+    pub wallet_address: [u8; 20],
+    pub rank: u32,
+    pub xp_amount: u64,
+    pub claimed: bool,
+}
 
-/// Messages sent between chains for state synchronization
-/// All tournament state updates go through the hub chain
-#[derive(Debug, Deserialize, Serialize)]
-pub enum Message {
-    /// Apply a run submission to hub chain state
-    /// Sent from user chain to hub chain
-    ApplyRun {
-        wallet_address: [u8; 20],
-        username: String,
-        tournament_id: u64,
-        time_ms: u64,
-        score: u64,
-        coins: u32,
-        deaths: u32,
-        completed: bool,
-    },
+/// Clan (team of players)
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct Clan {
+    pub id: u64,
+    pub name: String,
+    pub tag: String,
+    pub leader: [u8; 20],
+    pub member_count: u32,
+    pub total_xp: u64,                  // XP earned by members while in the clan
+    pub created_at: Timestamp,
 }
 
-// ============================================
-// OPERATIONS (Mutations)
-// ============================================
+/// Clan membership record
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct ClanMember {
+    pub clan_id: u64,
+    pub wallet_address: [u8; 20],
+    pub username: String,
+    pub role: ClanRole,
+    pub xp_contributed: u64,
+    pub joined_at: Timestamp,
+}
 
-#[derive(Debug, Deserialize, Serialize)]
-pub enum Operation {
-    /// Register a new player (wallet binding)
-    /// Called automatically on first submitRun if player doesn't exist
-    RegisterPlayer {
-        wallet_address: [u8; 20],
-        username: String,
-    },
-    
-    /// Submit a game run to a tournament
-    /// This is the PRIMARY gameplay operation
-    /// - Auto-registers player if not exists
-    /// - Validates tournament is active
-    /// - Updates player stats and leaderboard
-    SubmitRun {
-        tournament_id: u64,
-        time_ms: u64,
-        score: u64,
-        coins: u32,
-        deaths: u32,
-        completed: bool,
+/// Clan XP credited by one wallet's runs in a tournament (taken back by a Revert cancellation)
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ClanXpCredit {
+    pub clan_id: u64,
+    pub xp: u64,
+}
+
+/// Team leaderboard entry (sorted by score descending)
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct TeamLeaderboardEntry {
+    pub rank: u32,
+    pub clan_id: u64,
+    pub clan_name: String,
+    pub clan_tag: String,
+    pub score: u64,
+    pub contributing_members: u32,
+}
+
+/// One side's run in a duel
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct DuelRun {
+    pub time_ms: u64,
+    pub score: u64,
+    pub coins: u32,
+    pub deaths: u32,
+    pub completed: bool,
+    pub xp_earned: u64,                 // Same scoring as Difficulty::calculate_xp
+    pub submitted_at: Timestamp,
+}
+
+/// A duel run as submitted by one of its players (SubmitDuelRun)
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::InputObject)]
+pub struct DuelRunSubmission {
+    pub duel_id: u64,
+    pub time_ms: u64,
+    pub score: u64,
+    pub coins: u32,
+    pub deaths: u32,
+    pub completed: bool,
+}
+
+/// Head-to-head duel on a fresh maze seed
+/// Both players submit one run before the deadline; higher XP wins
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct Duel {
+    pub id: u64,
+    pub challenger: [u8; 20],
+    pub challenger_username: String,
+    pub opponent: Option<[u8; 20]>,     // None = open lobby, first to accept takes it
+    pub opponent_username: Option<String>,
+    pub maze_seed: String,
+    pub difficulty: Difficulty,
+    pub status: DuelStatus,
+    pub challenger_run: Option<DuelRun>,
+    pub opponent_run: Option<DuelRun>,
+    pub winner: Option<[u8; 20]>,       // None after completion = draw
+    pub created_at: Timestamp,
+    pub deadline: Timestamp,
+}
+
+impl Duel {
+    /// Decide the winner from both runs
+    /// Higher XP wins, then lower completed time; otherwise a draw
+    pub fn decide_winner(&self) -> Option<[u8; 20]> {
+        let opponent = self.opponent?;
+        let (a, b) = match (&self.challenger_run, &self.opponent_run) {
+            (Some(a), Some(b)) => (a, b),
+            (Some(_), None) => return Some(self.challenger),
+            (None, Some(_)) => return Some(opponent),
+            (None, None) => return None,
+        };
+
+        if a.xp_earned != b.xp_earned {
+            return Some(if a.xp_earned > b.xp_earned { self.challenger } else { opponent });
+        }
+        if a.completed && b.completed && a.time_ms != b.time_ms {
+            return Some(if a.time_ms < b.time_ms { self.challenger } else { opponent });
+        }
+        None
+    }
+}
+
+/// Global ratings leaderboard entry (sorted by rating descending)
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct RatingEntry {
+    pub rank: u32,
+    pub wallet_address: [u8; 20],
+    pub username: String,
+    pub rating: u32,
+    pub rating_deviation: u32,
+    pub provisional: bool,
+}
+
+/// Achievement unlocked by a player (persisted per wallet)
+#[derive(Clone, Debug, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct PlayerAchievement {
+    pub achievement: AchievementId,
+    pub wallet_address: [u8; 20],
+    pub tournament_id: u64,
+    pub run_id: Option<u64>,            // GameRun that triggered it (None for placement awards)
+    pub unlocked_at: Timestamp,
+}
+
+// ============================================
+// ACHIEVEMENTS
+// ============================================
+
+/// Achievement identifiers (one per catalogue entry)
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, async_graphql::Enum, PartialEq, Eq, Hash)]
+pub enum AchievementId {
+    FirstCompletion,    // Complete any maze
+    EasySpeedrun,       // Complete an Easy maze in under 30s
+    Centurion,          // Submit 100 runs
+    Podium,             // Finish a tournament in the top 3
+    Champion,           // Win a tournament
+    FlawlessNightmare,  // Complete a Nightmare maze without dying
+}
+
+/// Unlock condition for a catalogue entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AchievementCriterion {
+    /// Any completed run
+    Completion,
+    /// Completed run on `difficulty` faster than `time_ms`
+    CompletedUnder { difficulty: Difficulty, time_ms: u64 },
+    /// Completed run on `difficulty` with zero deaths
+    DeathlessCompletion { difficulty: Difficulty },
+    /// Lifetime run count reaches `runs`
+    TotalRuns { runs: u64 },
+    /// Final tournament rank at or above `rank`
+    FinalRank { rank: u32 },
+}
+
+/// Static catalogue entry
+#[derive(Clone, Copy, Debug)]
+pub struct AchievementDefinition {
+    pub id: AchievementId,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub criterion: AchievementCriterion,
+}
+
+/// The achievement catalogue - add new entries here
+/// Run-based criteria are evaluated in ApplyRun, placement criteria in end_tournament
+pub const ACHIEVEMENTS: &[AchievementDefinition] = &[
+    AchievementDefinition {
+        id: AchievementId::FirstCompletion,
+        name: "First Escape",
+        description: "Complete your first maze",
+        criterion: AchievementCriterion::Completion,
+    },
+    AchievementDefinition {
+        id: AchievementId::EasySpeedrun,
+        name: "Speed Demon",
+        description: "Complete an Easy maze in under 30 seconds",
+        criterion: AchievementCriterion::CompletedUnder { difficulty: Difficulty::Easy, time_ms: 30_000 },
+    },
+    AchievementDefinition {
+        id: AchievementId::Centurion,
+        name: "Centurion",
+        description: "Submit 100 runs",
+        criterion: AchievementCriterion::TotalRuns { runs: 100 },
+    },
+    AchievementDefinition {
+        id: AchievementId::Podium,
+        name: "Podium Finish",
+        description: "Finish a tournament in the top 3",
+        criterion: AchievementCriterion::FinalRank { rank: 3 },
+    },
+    AchievementDefinition {
+        id: AchievementId::Champion,
+        name: "Champion",
+        description: "Win a tournament",
+        criterion: AchievementCriterion::FinalRank { rank: 1 },
+    },
+    AchievementDefinition {
+        id: AchievementId::FlawlessNightmare,
+        name: "Flawless Nightmare",
+        description: "Complete a Nightmare maze without dying",
+        criterion: AchievementCriterion::DeathlessCompletion { difficulty: Difficulty::Nightmare },
+    },
+];
+
+impl AchievementCriterion {
+    /// Check a run-based criterion against a freshly applied run
+    /// `player` must already include the run in its totals
+    pub fn is_met_by_run(&self, difficulty: Difficulty, run: &GameRun, player: &Player) -> bool {
+        match *self {
+            AchievementCriterion::Completion => run.completed,
+            AchievementCriterion::CompletedUnder { difficulty: d, time_ms } => {
+                run.completed && difficulty == d && run.time_ms < time_ms
+            }
+            AchievementCriterion::DeathlessCompletion { difficulty: d } => {
+                run.completed && difficulty == d && run.deaths == 0
+            }
+            AchievementCriterion::TotalRuns { runs } => player.total_runs >= runs,
+            AchievementCriterion::FinalRank { .. } => false,
+        }
+    }
+
+    /// Check a placement criterion against a final tournament rank
+    pub fn is_met_by_placement(&self, final_rank: u32) -> bool {
+        match *self {
+            AchievementCriterion::FinalRank { rank } => final_rank >= 1 && final_rank <= rank,
+            _ => false,
+        }
+    }
+}
+
+/// Placement achievements earned on a final leaderboard, however many ranks the
+/// reward curve pays; only players who finished the maze place
+pub fn placement_achievements(leaderboard: &[LeaderboardEntry]) -> Vec<([u8; 20], AchievementId)> {
+    leaderboard.iter()
+        .filter(|entry| entry.best_time_ms < u64::MAX)
+        .flat_map(|entry| {
+            ACHIEVEMENTS.iter()
+                .filter(|definition| definition.criterion.is_met_by_placement(entry.rank))
+                .map(|definition| (entry.wallet_address, definition.id))
+        })
+        .collect()
+}
+
+// ============================================
+// XP CALCULATION
+// ============================================
+
+impl Difficulty {
+    /// Calculate XP earned for a run
+    /// Formula: base_xp + completion_bonus + time_bonus - death_penalty
+    pub fn calculate_xp(&self, time_ms: u64, deaths: u32, completed: bool) -> u64 {
+        let base = self.base_xp();
+        
+        // Completion bonus: +100% of base if completed
+        let completion_bonus = if completed { base } else { 0 };
+        
+        // Time bonus: up to +100% of base for fast completion (under 2 min)
+        let time_secs = time_ms / 1000;
+        let time_bonus = if completed && time_secs < 120 {
+            (base * (120 - time_secs)) / 120
+        } else {
+            0
+        };
+        
+        // Death penalty: -10% per death, max 50%
+        let death_penalty = std::cmp::min(deaths as u64 * 10, 50);
+        let death_multiplier = 100 - death_penalty;
+        
+        // Final calculation
+        let raw_xp = (base + completion_bonus + time_bonus) * death_multiplier / 100;
+        std::cmp::max(10, raw_xp)  // Minimum 10 XP
+    }
+}
+
+// ============================================
+// SEASON POINTS
+// ============================================
+
+/// Season points by final tournament rank (1st..10th)
+pub const SEASON_POINTS: [u64; 10] = [25, 18, 15, 12, 10, 8, 6, 4, 2, 1];
+
+/// Share of last season's points carried into the next season (soft reset)
+pub const SEASON_CARRYOVER_PERCENT: u64 = 20;
+
+/// Season points earned for a final tournament rank (0 outside the top 10)
+pub fn season_points_for_rank(rank: u32) -> u64 {
+    match rank {
+        0 => 0,
+        r => SEASON_POINTS.get((r - 1) as usize).copied().unwrap_or(0),
+    }
+}
+
+// ============================================
+// DUELS
+// ============================================
+
+/// Allowed duel deadline range (hours)
+pub const MIN_DUEL_HOURS: u64 = 1;
+pub const MAX_DUEL_HOURS: u64 = 7 * 24;
+
+/// Secret mixed into duel seeds: derive_maze_seed(entropy, DUEL_SEED_DOMAIN)
+pub const DUEL_SEED_DOMAIN: &[u8] = b"duel";
+
+// ============================================
+// STAGE SPLITS
+// ============================================
+
+/// Maximum stages per run
+pub const MAX_STAGES: usize = 5;
+
+/// Validate per-stage splits against the run totals
+/// Empty splits are allowed (single-stage clients)
+pub fn validate_splits(
+    splits: &[StageSplit],
+    time_ms: u64,
+    deaths: u32,
+    coins: u32,
+) -> Result<(), String> {
+    if splits.is_empty() {
+        return Ok(());
+    }
+    if splits.len() > MAX_STAGES {
+        return Err(format!("At most {} stages per run", MAX_STAGES));
+    }
+    if splits.iter().enumerate().any(|(i, s)| s.stage != i as u32 + 1) {
+        return Err("Splits must be numbered 1..n in order".to_string());
+    }
+
+    let total_time: u64 = splits.iter().map(|s| s.time_ms).sum();
+    let total_deaths: u32 = splits.iter().map(|s| s.deaths).sum();
+    let total_coins: u32 = splits.iter().map(|s| s.coins).sum();
+    if total_time != time_ms || total_deaths != deaths || total_coins != coins {
+        return Err("Splits do not add up to the run totals".to_string());
+    }
+    Ok(())
+}
+
+// ============================================
+// REWARD CURVES & TEMPLATES
+// ============================================
+
+/// Default reward curve: 1st=40%, 2nd=25%, 3rd=15%, 4th=12%, 5th=8%
+pub const DEFAULT_REWARD_PERCENTAGES: [u64; 5] = [40, 25, 15, 12, 8];
+
+/// Maximum rewarded ranks per tournament
+pub const MAX_REWARD_SLOTS: usize = 10;
+
+/// Check a reward curve pays at most the whole pool to at most MAX_REWARD_SLOTS ranks
+pub fn validate_reward_curve(curve: &[u64]) -> Result<(), String> {
+    if curve.is_empty() || curve.len() > MAX_REWARD_SLOTS {
+        return Err(format!("Reward curve must have 1-{} ranks", MAX_REWARD_SLOTS));
+    }
+    if curve.iter().sum::<u64>() > 100 {
+        return Err("Reward curve exceeds 100%".to_string());
+    }
+    Ok(())
+}
+
+impl TournamentTemplate {
+    /// Title of the n-th instance
+    pub fn instance_title(&self, instance: u32) -> String {
+        self.title_pattern.replace("{n}", &instance.to_string())
+    }
+
+    /// Parameters of the n-th instance (seeded from chain entropy)
+    pub fn instance_params(&self, instance: u32) -> TournamentParams {
+        TournamentParams {
+            title: self.instance_title(instance),
+            description: self.description.clone(),
+            seed_commitment: None,
+            difficulty: self.difficulty,
+            duration_micros: self.duration_hours * 60 * 60 * 1_000_000,
+            xp_reward_pool: self.xp_reward_pool,
+            team_mode: self.team_mode,
+            team_best_n: self.team_best_n,
+            commit_reveal_required: false,
+            seed_mode: self.seed_mode,
+            maze_config: self.maze_config.clone(),
+            reward_percentages: self.reward_percentages.clone(),
+            template_id: Some(self.id),
+        }
+    }
+
+    /// First recurrence slot strictly after `now`
+    pub fn next_slot_after(&self, now: Timestamp) -> Timestamp {
+        let period = self.recurrence.period_micros();
+        let start = self.next_start.micros();
+        if now.micros() < start {
+            return self.next_start;
+        }
+        let skipped = (now.micros() - start) / period + 1;
+        Timestamp::from(start + skipped * period)
+    }
+}
+
+// ============================================
+// MAZE CONFIG
+// ============================================
+
+/// Maze side length bounds (cells)
+pub const MIN_MAZE_SIZE: u32 = 4;
+pub const MAX_MAZE_SIZE: u32 = 50;
+
+/// Maximum trap density (percent of cells)
+pub const MAX_TRAP_DENSITY_PERCENT: u32 = 30;
+
+impl MazeConfig {
+    /// Check the config can be generated and played
+    pub fn validate(&self) -> Result<(), String> {
+        for side in [self.width, self.height] {
+            if !(MIN_MAZE_SIZE..=MAX_MAZE_SIZE).contains(&side) {
+                return Err(format!("Maze sides must be {}-{} cells", MIN_MAZE_SIZE, MAX_MAZE_SIZE));
+            }
+        }
+        if self.stages == 0 || self.stages as usize > MAX_STAGES {
+            return Err(format!("Stages must be 1-{}", MAX_STAGES));
+        }
+        if self.trap_density_percent > MAX_TRAP_DENSITY_PERCENT {
+            return Err(format!("Trap density must be at most {}%", MAX_TRAP_DENSITY_PERCENT));
+        }
+        if self.stage_sizes.len() != self.stages as usize || self.stage_coins.len() != self.stages as usize {
+            return Err("Stage sizes and coins must list every stage".to_string());
+        }
+        for (&size, &coins) in self.stage_sizes.iter().zip(&self.stage_coins) {
+            if !(MIN_MAZE_SIZE..=MAX_MAZE_SIZE).contains(&size) {
+                return Err(format!("Maze sides must be {}-{} cells", MIN_MAZE_SIZE, MAX_MAZE_SIZE));
+            }
+            if coins >= size * size {
+                return Err("Too many coins for the maze size".to_string());
+            }
+        }
+        if self.time_limit_ms == Some(0) {
+            return Err("Time limit must be positive".to_string());
+        }
+        Ok(())
+    }
+
+    /// Check a run's totals fit this maze
+    /// A completed run with splits must have one per stage (no splits = single-stage client)
+    pub fn check_run(
+        &self,
+        time_ms: u64,
+        coins: u32,
+        completed: bool,
+        splits: &[StageSplit],
+    ) -> Result<(), String> {
+        if splits.len() > self.stages as usize {
+            return Err("More splits than stages".to_string());
+        }
+        if completed && !splits.is_empty() && splits.len() != self.stages as usize {
+            return Err("Completed run is missing stage splits".to_string());
+        }
+        if coins > self.stage_coins.iter().sum::<u32>()
+            || splits.iter().zip(&self.stage_coins).any(|(split, &held)| split.coins > held)
+        {
+            return Err("More coins than the maze holds".to_string());
+        }
+        if self.time_limit_ms.is_some_and(|limit| time_ms > limit) {
+            return Err("Run exceeds the time limit".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Stages cleared in a run: every split except an unfinished last stage
+pub fn cleared_splits(splits: &[StageSplit], completed: bool) -> &[StageSplit] {
+    match splits.len() {
+        0 => splits,
+        n if completed => &splits[..n],
+        n => &splits[..n - 1],
+    }
+}
+
+/// Insert or improve a wallet's time on a split leaderboard (keeps top 100)
+pub fn upsert_split_entry(
+    board: &mut Vec<SplitLeaderboardEntry>,
+    wallet_address: [u8; 20],
+    username: &str,
+    time_ms: u64,
+) {
+    match board.iter_mut().find(|e| e.wallet_address == wallet_address) {
+        Some(entry) if time_ms < entry.time_ms => entry.time_ms = time_ms,
+        Some(_) => return,
+        None => board.push(SplitLeaderboardEntry {
+            rank: 0,
+            wallet_address,
+            username: username.to_string(),
+            time_ms,
+        }),
+    }
+
+    board.sort_by_key(|e| e.time_ms);
+    board.truncate(100);
+    for (i, entry) in board.iter_mut().enumerate() {
+        entry.rank = (i + 1) as u32;
+    }
+}
+
+// ============================================
+// RUN TICKETS
+// ============================================
+
+/// Time a run ticket stays valid after issue (1 hour)
+pub const RUN_TICKET_TTL_MICROS: u64 = 60 * 60 * 1_000_000;
+
+/// Maximum unused, unexpired tickets per wallet
+pub const MAX_OPEN_RUN_TICKETS: usize = 20;
+
+impl RunTicket {
+    pub fn expired(&self, now: Timestamp) -> bool {
+        now.micros().saturating_sub(self.issued_at.micros()) > RUN_TICKET_TTL_MICROS
+    }
+
+    /// Check a ticket can admit a run of `time_ms` in `tournament_id` at `now`
+    pub fn validate(&self, tournament_id: u64, time_ms: u64, now: Timestamp) -> Result<(), String> {
+        if self.tournament_id != tournament_id {
+            return Err("Run ticket is for another tournament".to_string());
+        }
+
+        let elapsed = now.micros().saturating_sub(self.issued_at.micros());
+        if self.expired(now) {
+            return Err("Run ticket expired".to_string());
+        }
+        if elapsed < time_ms.saturating_mul(1_000) {
+            return Err("Claimed time exceeds time since ticket issue".to_string());
+        }
+        Ok(())
+    }
+}
+
+// ============================================
+// SEED FAIRNESS
+// ============================================
+
+/// Per-attempt seed: hex(sha3_256(len(seed) as u32 LE || seed || ticket entropy))
+/// The entropy is captured by the host when it issues the ticket, so the seed can't be
+/// previewed before then; anyone can recompute it from a run's stored ticket_entropy
+pub fn derive_attempt_seed(tournament_seed: &str, ticket_entropy: &[u8; 32]) -> String {
+    use sha3::{Digest, Sha3_256};
+
+    let mut hasher = Sha3_256::new();
+    hasher.update((tournament_seed.len() as u32).to_le_bytes());
+    hasher.update(tournament_seed.as_bytes());
+    hasher.update(ticket_entropy);
+    let digest: [u8; 32] = hasher.finalize().into();
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Shortest start-to-exit path of the maze the client generates for `seed`
+pub fn maze_path_length(seed: &str, config: &MazeConfig) -> u32 {
+    match config.algorithm {
+        MazeAlgorithm::RecursiveBacktracker => {
+            maze::Maze::generate(seed, config.width as usize, config.height as usize)
+                .shortest_path_length()
+        }
+    }
+}
+
+/// Scale a time to the reference maze: time * reference / path_length
+pub fn normalize_time(time_ms: u64, path_length: u32, reference_path_length: u32) -> u64 {
+    if path_length == 0 || reference_path_length == 0 {
+        return time_ms;
+    }
+    (time_ms as u128 * reference_path_length as u128 / path_length as u128) as u64
+}
+
+/// Maximum organizer seed secret size (bytes)
+pub const MAX_SEED_SECRET_BYTES: usize = 256;
+
+/// Chain entropy for a tournament seed:
+/// sha3_256 of the creating chain, application, block height, time and tournament id
+pub fn compute_seed_entropy(
+    chain_id: &str,
+    application_id: &str,
+    block_height: u64,
+    micros: u64,
+    tournament_id: u64,
+) -> [u8; 32] {
+    use sha3::{Digest, Sha3_256};
+
+    let mut hasher = Sha3_256::new();
+    for part in [chain_id, application_id] {
+        hasher.update((part.len() as u32).to_le_bytes());
+        hasher.update(part.as_bytes());
+    }
+    hasher.update(block_height.to_le_bytes());
+    hasher.update(micros.to_le_bytes());
+    hasher.update(tournament_id.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Organizer commitment to a seed secret: sha3_256(secret)
+pub fn compute_seed_commitment(secret: &[u8]) -> [u8; 32] {
+    use sha3::{Digest, Sha3_256};
+
+    Sha3_256::digest(secret).into()
+}
+
+/// Tournament maze seed: hex(sha3_256(entropy || secret))
+/// Without an organizer commitment the secret is empty
+pub fn derive_maze_seed(entropy: &[u8; 32], secret: &[u8]) -> String {
+    use sha3::{Digest, Sha3_256};
+
+    let mut hasher = Sha3_256::new();
+    hasher.update(entropy);
+    hasher.update(secret);
+    let digest: [u8; 32] = hasher.finalize().into();
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+impl Tournament {
+    /// Check the published seed follows from the entropy and the committed secret
+    pub fn verify_seed(&self) -> bool {
+        if !self.seed_revealed {
+            return false;
+        }
+        let secret = self.seed_secret.as_deref().unwrap_or_default();
+        if let Some(commitment) = self.seed_commitment {
+            if compute_seed_commitment(secret) != commitment {
+                return false;
+            }
+        }
+        derive_maze_seed(&self.seed_entropy, secret) == self.maze_seed
+    }
+
+    /// Seed a run plays under a ticket issued with this entropy
+    pub fn attempt_seed(&self, ticket_entropy: &[u8; 32]) -> String {
+        match self.seed_mode {
+            SeedMode::Shared => self.maze_seed.clone(),
+            SeedMode::PerAttempt => derive_attempt_seed(&self.maze_seed, ticket_entropy),
+        }
+    }
+}
+
+// ============================================
+// COMMIT-REVEAL
+// ============================================
+
+/// Time allowed between CommitRun and the reveal (1 hour)
+pub const REVEAL_WINDOW_MICROS: u64 = 60 * 60 * 1_000_000;
+
+/// Maximum revealed move trace size (bytes)
+pub const MAX_REVEAL_BYTES: usize = 64 * 1024;
+
+/// Commitment binding a run to its maze seed:
+/// sha3_256(len(seed) as u32 LE || seed || len(moves) as u32 LE || moves || salt)
+pub fn compute_run_commitment(seed: &str, moves: &[u8], salt: &[u8]) -> [u8; 32] {
+    use sha3::{Digest, Sha3_256};
+
+    let mut hasher = Sha3_256::new();
+    hasher.update((seed.len() as u32).to_le_bytes());
+    hasher.update(seed.as_bytes());
+    hasher.update((moves.len() as u32).to_le_bytes());
+    hasher.update(moves);
+    hasher.update(salt);
+    hasher.finalize().into()
+}
+
+impl RunCommitment {
+    /// Check a reveal: hash must match and enough wall-clock time must have passed
+    pub fn verify(
+        &self,
+        seed: &str,
+        reveal: &RunReveal,
+        time_ms: u64,
+        now: Timestamp,
+    ) -> Result<(), String> {
+        if compute_run_commitment(seed, &reveal.moves, &reveal.salt) != self.commitment {
+            return Err("Reveal does not match commitment".to_string());
+        }
+
+        let elapsed = now.micros().saturating_sub(self.committed_at.micros());
+        if elapsed > REVEAL_WINDOW_MICROS {
+            return Err("Reveal window expired".to_string());
+        }
+        if elapsed < time_ms.saturating_mul(1_000) {
+            return Err("Claimed time exceeds time since commit".to_string());
+        }
+        Ok(())
+    }
+}
+
+// ============================================
+// GHOST REPLAYS
+// ============================================
+
+/// Maximum ghost trace size (bytes, already compressed by the client)
+pub const MAX_GHOST_BYTES: usize = 32 * 1024;
+
+/// Leaderboard ranks that keep their ghost replay
+pub const GHOST_LEADERBOARD_SLOTS: u32 = 10;
+
+// ============================================
+// CLANS
+// ============================================
+
+/// Maximum members per clan
+pub const MAX_CLAN_MEMBERS: u32 = 50;
+
+/// Clan tag length bounds (characters)
+pub const MIN_CLAN_TAG_LEN: usize = 2;
+pub const MAX_CLAN_TAG_LEN: usize = 5;
+
+impl TeamMode {
+    /// Clan score from its members' best scores in a tournament
+    /// Returns (score, contributing_members)
+    pub fn team_score(&self, mut member_scores: Vec<u64>, best_n: u32) -> (u64, u32) {
+        member_scores.sort_by_key(|s| std::cmp::Reverse(*s));
+        if *self == TeamMode::TeamBestN {
+            member_scores.truncate(best_n as usize);
+        }
+        (member_scores.iter().sum(), member_scores.len() as u32)
+    }
+}
+
+// ============================================
+// SKILL RATING (GLICKO)
+// ============================================
+
+/// Rating assigned to new players
+pub const DEFAULT_RATING: u32 = 1500;
+/// Rating deviation assigned to new players (maximum uncertainty)
+pub const DEFAULT_RATING_DEVIATION: u32 = 350;
+/// Floor for rating deviation so ratings never freeze completely
+pub const MIN_RATING_DEVIATION: u32 = 30;
+/// Ratings with a deviation above this are provisional
+pub const PROVISIONAL_RATING_DEVIATION: u32 = 110;
+
+/// Glicko-1 update treating final standings as one multi-player match
+/// Each participant plays every other participant once: a lower `best_time_ms`
+/// wins, equal times draw. Input is (rating, rating_deviation, best_time_ms),
+/// output is the new (rating, rating_deviation) in the same order.
+pub fn glicko_update(standings: &[(u32, u32, u64)]) -> Vec<(u32, u32)> {
+    let q = std::f64::consts::LN_10 / 400.0;
+    let pi_sq = std::f64::consts::PI * std::f64::consts::PI;
+    let g = |rd: f64| 1.0 / (1.0 + 3.0 * q * q * rd * rd / pi_sq).sqrt();
+
+    standings.iter().enumerate().map(|(i, &(rating, rd, time))| {
+        let r = rating as f64;
+        let rd = rd as f64;
+
+        let mut d_inv = 0.0;
+        let mut delta = 0.0;
+        for (j, &(opp_rating, opp_rd, opp_time)) in standings.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let g_j = g(opp_rd as f64);
+            let expected = 1.0 / (1.0 + 10f64.powf(-g_j * (r - opp_rating as f64) / 400.0));
+            let score = match time.cmp(&opp_time) {
+                std::cmp::Ordering::Less => 1.0,
+                std::cmp::Ordering::Equal => 0.5,
+                std::cmp::Ordering::Greater => 0.0,
+            };
+            d_inv += q * q * g_j * g_j * expected * (1.0 - expected);
+            delta += g_j * (score - expected);
+        }
+
+        if d_inv == 0.0 {
+            return (rating, rd as u32);
+        }
+
+        let precision = 1.0 / (rd * rd) + d_inv;
+        let new_rating = (r + q / precision * delta).round().max(0.0);
+        let new_rd = (1.0 / precision).sqrt().round()
+            .clamp(MIN_RATING_DEVIATION as f64, DEFAULT_RATING_DEVIATION as f64);
+        (new_rating as u32, new_rd as u32)
+    }).collect()
+}
+
+// ============================================
+// CROSS-CHAIN MESSAGES
+// ============================================
+
+/// Messages sent between chains for state synchronization
+/// All tournament state updates go through the hub chain
+#[derive(Debug, Deserialize, Serialize)]
+pub enum Message {
+    /// Apply a run submission to hub chain state
+    /// Sent from user chain to hub chain
+    ApplyRun {
+        wallet_address: [u8; 20],
+        username: String,
+        run: RunSubmission,
+        receipt_id: u64,                // Submitting chain's RunReceipt (0 = no receipt wanted)
+    },
+
+    /// Run applied: reply to ApplyRun
+    /// Sent from hub chain to the submitting chain
+    RunAccepted {
+        receipt_id: u64,
+        run_id: u64,
+        xp_earned: u64,
+        rank: Option<u32>,
+    },
+
+    /// Run refused: reply to ApplyRun
+    /// Sent from hub chain to the submitting chain
+    RunRejected {
+        receipt_id: u64,
+        reason: String,
+    },
+
+    /// Apply a batch of runs by one player to one tournament in a single pass
+    /// Sent from user chain to the tournament's chain
+    ApplyRuns {
+        wallet_address: [u8; 20],
+        username: String,
+        tournament_id: u64,
+        runs: Vec<RunSubmission>,
+        first_receipt_id: u64,          // Receipts are consecutive, one per run (0 = none wanted)
+    },
+
+    /// Per-run results: reply to ApplyRuns
+    /// Sent from hub chain to the submitting chain
+    RunsApplied {
+        first_receipt_id: u64,
+        results: Vec<RunResult>,        // In submission order
+        rank: Option<u32>,              // Leaderboard rank after the batch
+    },
+
+    /// Issue a run ticket (hub chain timestamps it)
+    /// Sent from user chain to hub chain
+    IssueTicket {
+        wallet_address: [u8; 20],
+        tournament_id: u64,
+        nonce: u64,
+    },
+
+    /// Record a run commitment at run start (hub chain timestamps it)
+    /// Sent from user chain to hub chain
+    CommitRun {
+        wallet_address: [u8; 20],
+        tournament_id: u64,
+        commitment: [u8; 32],
+    },
+
+    /// Create a clan with the sender as leader
+    /// Sent from user chain to hub chain
+    CreateClan {
+        wallet_address: [u8; 20],
+        username: String,
+        name: String,
+        tag: String,
+    },
+
+    /// Invite a wallet to the sender's clan (leader/officer only)
+    /// Sent from user chain to hub chain
+    InviteToClan {
+        wallet_address: [u8; 20],
+        invitee: [u8; 20],
+    },
+
+    /// Join a clan the sender was invited to
+    /// Sent from user chain to hub chain
+    JoinClan {
+        wallet_address: [u8; 20],
+        username: String,
+        clan_id: u64,
+    },
+
+    /// Leave the sender's clan
+    /// Sent from user chain to hub chain
+    LeaveClan {
+        wallet_address: [u8; 20],
+    },
+
+    /// Change a member's role (leader only)
+    /// Sent from user chain to hub chain
+    SetClanRole {
+        wallet_address: [u8; 20],
+        member: [u8; 20],
+        role: ClanRole,
+    },
+
+    /// Open a duel on the hub chain
+    /// Sent from user chain to hub chain
+    OpenDuel {
+        challenger: [u8; 20],
+        username: String,
+        opponent: Option<[u8; 20]>,
+        difficulty: Difficulty,
+        duration_hours: u64,
+    },
+
+    /// Accept an open or directed duel
+    /// Sent from user chain to hub chain
+    AcceptDuel {
+        wallet_address: [u8; 20],
+        username: String,
+        duel_id: u64,
+    },
+
+    /// Push player snapshots to the sending chain (refreshes immediately)
+    /// Sent from user chain to hub chain
+    SubscribeSnapshots {
+        wallet_address: [u8; 20],
+        tournament_id: Option<u64>,     // Tournament to include (default: featured)
+    },
+
+    /// Stop pushing player snapshots to the sending chain
+    /// Sent from user chain to hub chain
+    UnsubscribeSnapshots {
+        wallet_address: [u8; 20],
+    },
+
+    /// Authoritative player state for a user chain's local cache
+    /// Sent from hub chain to subscribed user chains
+    PlayerSnapshot {
+        wallet_address: [u8; 20],
+        player: Box<Player>,
+        tournament: Option<Box<Tournament>>,
+        tournament_player: Option<Box<TournamentPlayer>>,
+        rank: Option<u32>,                      // Leaderboard rank (None outside the top 100)
+    },
+
+    /// Push this tournament's leaderboard changes to the sending chain
+    /// (answers with the full board first)
+    /// Sent from user chain to the tournament's chain
+    SubscribeLeaderboard {
+        tournament_id: u64,
+    },
+
+    /// Stop pushing this tournament's leaderboard to the sending chain
+    /// Sent from user chain to the tournament's chain
+    UnsubscribeLeaderboard {
+        tournament_id: u64,
+    },
+
+    /// Leaderboard rows that changed since the last delta (the whole board when `reset`)
+    /// Sent from the tournament's chain to subscribed user chains
+    LeaderboardDelta {
+        tournament_id: u64,
+        changes: Vec<LeaderboardChange>,
+        removed: Vec<[u8; 20]>,         // Wallets that dropped off the board
+        reset: bool,                    // Replace the cached board rather than patch it
+    },
+
+    /// Make the receiving shard authoritative for a tournament's runs, or refresh
+    /// its copy after the hub amended, revealed or cancelled it
+    /// Sent from hub chain to shard chain
+    HostTournament {
+        tournament: Box<Tournament>,
+    },
+
+    /// Ask the hub's directory which chain owns a tournament
+    /// Sent from user chain to hub chain
+    LocateTournament {
+        tournament_id: u64,
+    },
+
+    /// Directory answer: send this tournament's runs to `chain_id`
+    /// Sent from hub chain to user chains (also after a misrouted message)
+    TournamentLocation {
+        tournament_id: u64,
+        chain_id: ChainId,
+    },
+
+    /// A hosted tournament was finalized; the hub settles rewards, season points and ratings
+    /// Sent from shard chain to hub chain
+    ShardTournamentUpdate {
+        tournament: Box<Tournament>,
+    },
+
+    /// Runs a shard accepted, for the hub to mirror and credit to the player
+    /// Sent from shard chain to hub chain after each ApplyRun(s)
+    ShardRunsApplied {
+        wallet_address: [u8; 20],
+        username: String,
+        tournament_id: u64,
+        participant_count: u32,         // Tournament counters after the batch
+        total_runs: u64,
+        new_participant: bool,
+        tournament_player: Box<TournamentPlayer>,
+        runs: Vec<GameRun>,             // Accepted runs, in submission order
+        changes: Vec<LeaderboardChange>,
+        removed: Vec<[u8; 20]>,
+    },
+
+    /// Finalize a hosted tournament if it is over (EndTournament / FinalizeExpired on the hub)
+    /// Sent from hub chain to shard chain
+    FinalizeTournament {
+        tournament_id: u64,
+    },
+
+    /// Apply a duel run to hub chain state
+    /// Sent from user chain to hub chain
+    ApplyDuelRun {
+        wallet_address: [u8; 20],
+        run: DuelRunSubmission,
+    },
+
+    /// Point the receiving chain at a (new) hub chain
+    /// Sent from the hub chain to user chains and the new hub; a non-hub
+    /// chain also sends it back to chains that message it by mistake
+    SetHub {
+        hub_chain_id: ChainId,
+        epoch: u32,                     // Bumped by every MigrateHub; stale updates are ignored
+        retired: Vec<ChainId>,          // Former hub chains, whose messages the hub rejects
+    },
+}
+
+impl Message {
+    /// Tournament a message acts on (routes it to the tournament's chain and
+    /// drives lazy finalization); None for messages the hub handles itself
+    pub fn tournament_id(&self) -> Option<u64> {
+        match self {
+            Message::ApplyRun { run, .. } => Some(run.tournament_id),
+            Message::ApplyRuns { tournament_id, .. }
+            | Message::IssueTicket { tournament_id, .. }
+            | Message::SubscribeLeaderboard { tournament_id }
+            | Message::UnsubscribeLeaderboard { tournament_id }
+            | Message::FinalizeTournament { tournament_id }
+            | Message::CommitRun { tournament_id, .. } => Some(*tournament_id),
+            _ => None,
+        }
+    }
+
+    /// Chains the receiving host accepts this message from
+    pub fn allowed_origin(&self) -> MessageOrigin {
+        match self {
+            Message::ShardTournamentUpdate { tournament } => MessageOrigin::TournamentShard(tournament.id),
+            Message::ShardRunsApplied { tournament_id, .. } => MessageOrigin::TournamentShard(*tournament_id),
+            Message::FinalizeTournament { .. } => MessageOrigin::Hub,
+            _ => MessageOrigin::AnyChain,
+        }
+    }
+}
+
+/// Origin rule for messages bound for a tournament's host or the hub
+/// (retired hubs are refused whatever the rule)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageOrigin {
+    /// Any chain: user chains, shards and the host itself
+    AnyChain,
+    /// Only the shard on record as hosting this tournament
+    TournamentShard(u64),
+    /// Only the hub chain
+    Hub,
+}
+
+// ============================================
+// OPERATIONS (Mutations)
+// ============================================
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum Operation {
+    /// Register a new player (wallet binding)
+    /// Called automatically on first submitRun if player doesn't exist
+    RegisterPlayer {
+        wallet_address: [u8; 20],
+        username: String,
+    },
+    
+    /// Submit a game run to a tournament
+    /// This is the PRIMARY gameplay operation
+    /// - Auto-registers player if not exists
+    /// - Validates tournament is active
+    /// - Updates player stats and leaderboard
+    /// - Optional per-stage splits must add up to the totals
+    /// - Optional ghost trace is kept only if the run is a top-N personal best
+    /// - Optional reveal verifies a prior CommitRun (required if the tournament says so)
+    /// - Must consume an unused, unexpired ticket from StartRun
+    SubmitRun {
+        run: RunSubmission,
+    },
+
+    /// Submit several runs at once (e.g. after playing offline)
+    /// All runs must be for the same tournament, at most MAX_BATCH_RUNS;
+    /// each is validated like SubmitRun and gets its own receipt
+    SubmitRuns {
+        runs: Vec<RunSubmission>,
+    },
+
+    /// Start a run: the hub issues a ticket keyed by (wallet, nonce)
+    /// The client picks a fresh random nonce and quotes it in SubmitRun
+    /// The ticket carries the attempt's maze seed (query runTicket once it is issued)
+    StartRun {
+        tournament_id: u64,
+        nonce: u64,
+    },
+
+    /// Commit to a run before playing: hash(seed, moves, salt)
+    /// Replaces any pending commitment for this tournament
+    CommitRun {
+        tournament_id: u64,
+        commitment: [u8; 32],
+    },
+
+    /// Have the hub push this player's profile, tournament and rank to this
+    /// chain after every change, so reads here don't need the hub
+    /// Send again to force a refresh
+    SubscribePlayerSnapshots {
+        tournament_id: Option<u64>,
+    },
+
+    /// Stop snapshot pushes to this chain (the cached copy stays)
+    UnsubscribePlayerSnapshots,
+
+    /// Have the tournament's chain push leaderboard deltas to this chain, so
+    /// the leaderboard query here stays current without polling the hub
+    /// Send again to force a full refresh
+    SubscribeLeaderboard {
+        tournament_id: u64,
+    },
+
+    /// Stop leaderboard pushes to this chain (the cached copy stays)
+    UnsubscribeLeaderboard {
+        tournament_id: u64,
     },
     
     /// Create a new tournament (admin only)
+    /// The maze seed is derived from chain entropy and, if committed, an organizer
+    /// secret - runs open once RevealTournamentSeed publishes that secret
     CreateTournament {
         title: String,
         description: String,
-        maze_seed: String,
+        seed_commitment: Option<[u8; 32]>,
         difficulty: Difficulty,
         duration_days: u64,
         xp_reward_pool: u64,
+        team_mode: TeamMode,
+        team_best_n: u32,
+        commit_reveal_required: bool,
+        seed_mode: SeedMode,
+        maze_config: MazeConfig,
+        reward_percentages: Vec<u64>,
+    },
+    
+    /// Register a recurring tournament template (hub chain only)
+    CreateTemplate {
+        template: TemplateInput,
+    },
+
+    /// Pause or resume a template (hub chain only)
+    SetTemplateActive {
+        template_id: u64,
+        active: bool,
+    },
+
+    /// Finalize expired template instances and spawn the next ones (anyone)
+    RotateTournaments,
+    
+    /// Amend an active tournament (organizer, or any operation on the hub chain)
+    /// Every changed field is recorded in the tournament's amendment log
+    UpdateTournament {
+        tournament_id: u64,
+        update: TournamentUpdate,
+    },
+    
+    /// Cancel an active tournament (organizer, or any operation on the hub chain)
+    /// Stops accepting runs and never creates rewards
+    CancelTournament {
+        tournament_id: u64,
+        xp_policy: CancelXpPolicy,
+    },
+    
+    /// Publish the organizer secret committed at creation and fix the maze seed
+    RevealTournamentSeed {
+        tournament_id: u64,
+        secret: Vec<u8>,
     },
     
     /// End a tournament and compute rewards (admin only)
@@ -228,16 +1840,146 @@ pub enum Operation {
     },
     
     /// Claim XP reward from ended tournament
+    /// Finalizes the tournament first if it is overdue
     ClaimReward {
         tournament_id: u64,
     },
+
+    /// One-shot migration: merge the deprecated active_tournament register into
+    /// the tournaments MapView and clear it (anyone, idempotent)
+    ReconcileTournaments,
+
+    /// Finalize up to `limit` overdue tournaments (anyone)
+    /// Overdue tournaments are also finalized lazily by the first operation,
+    /// message or claim that touches them after end_time
+    FinalizeExpired {
+        limit: u32,
+    },
+
+    /// Rewrite up to `limit` stored records in the current schema layout (anyone)
+    /// Records decode from older layouts either way; once every table has been
+    /// rewritten the schema version is bumped and ReconcileTournaments runs
+    /// Cost: views cannot seek to a key, so each batch scans the table's keys from
+    /// the start up to its cursor; a full migration reads O(n^2 / limit) keys
+    Migrate {
+        limit: u32,
+    },
+
+    /// Move the hub to another chain (hub chain only)
+    /// Known user chains and the new hub are notified; chains that message the
+    /// old hub afterwards are told where it went. State is not copied: the new
+    /// hub starts from its own (usually empty) view
+    MigrateHub {
+        hub_chain_id: String,
+    },
+
+    /// Add a shard chain (hub chain only)
+    /// New tournaments are spread round-robin over the shards and run there;
+    /// the hub keeps a directory entry and a copy for listings
+    AddShard {
+        chain_id: String,
+    },
+
+    /// Stop assigning new tournaments to a shard (hub chain only)
+    /// Tournaments it already hosts stay there
+    RemoveShard {
+        chain_id: String,
+    },
+
+    /// Look up which chain hosts a tournament and cache it on this chain (anyone)
+    LocateTournament {
+        tournament_id: u64,
+    },
     
-    /// Bootstrap the 15-day tournament #1 if it doesn't exist
+    /// Start a new season (hub chain only)
+    /// Tournaments created while the season is active count towards it
+    /// Carries SEASON_CARRYOVER_PERCENT of the previous season's points over
+    CreateSeason {
+        title: String,
+        duration_days: u64,
+        xp_reward_pool: u64,
+    },
+
+    /// End a season and compute rewards (anyone, after end_time)
+    EndSeason {
+        season_id: u64,
+    },
+
+    /// Claim XP reward from ended season
+    ClaimSeasonReward {
+        season_id: u64,
+    },
+
+    /// Create a clan (you become its leader)
+    CreateClan {
+        name: String,
+        tag: String,
+    },
+
+    /// Invite a wallet to your clan (leader/officer)
+    InviteToClan {
+        invitee: [u8; 20],
+    },
+
+    /// Join a clan you were invited to
+    JoinClan {
+        clan_id: u64,
+    },
+
+    /// Leave your clan (leadership passes on; last member disbands it)
+    LeaveClan,
+
+    /// Change a member's role (leader); making someone Leader hands over leadership
+    SetClanRole {
+        member: [u8; 20],
+        role: ClanRole,
+    },
+
+    /// Challenge another wallet (or open a lobby when opponent is None)
+    /// The hub assigns a fresh maze seed and a deadline
+    CreateDuel {
+        opponent: Option<[u8; 20]>,
+        difficulty: Difficulty,
+        duration_hours: u64,
+    },
+
+    /// Accept a duel (directed challenge or open lobby)
+    AcceptDuel {
+        duel_id: u64,
+    },
+
+    /// Submit your single run for a duel
+    SubmitDuelRun {
+        run: DuelRunSubmission,
+    },
+
+    /// Settle a duel after its deadline (anyone)
+    /// A missing run forfeits; no runs at all expires the duel
+    ResolveDuel {
+        duel_id: u64,
+    },
+
+    /// Seed the default championship template and spawn tournament #1 if it doesn't exist
     /// This is a workaround for instantiate not persisting state
     /// Can be called by anyone - idempotent (no-op if tournament exists)
     BootstrapTournament,
 }
 
+impl Operation {
+    /// Tournament an operation administers or claims from; only the hub runs it
+    /// (changes to a sharded tournament are then forwarded to its shard)
+    pub fn hub_tournament_id(&self) -> Option<u64> {
+        match self {
+            Operation::UpdateTournament { tournament_id, .. }
+            | Operation::CancelTournament { tournament_id, .. }
+            | Operation::RevealTournamentSeed { tournament_id, .. }
+            | Operation::EndTournament { tournament_id }
+            | Operation::ClaimReward { tournament_id } => Some(*tournament_id),
+            _ => None,
+        }
+    }
+}
+
 // ============================================
 // RESPONSES
 // ============================================
@@ -246,6 +1988,14 @@ pub enum Operation {
 pub enum Response {
     Ok,
     
+    RunQueued {
+        receipt_id: u64,                // Track it with the runReceipts query
+    },
+    
+    RunsQueued {
+        receipt_ids: Vec<u64>,          // One per run, in submission order
+    },
+    
     PlayerRegistered {
         wallet_address: [u8; 20],
     },
@@ -263,6 +2013,55 @@ pub enum Response {
         end_time: Timestamp,
     },
     
+    TournamentSeedRevealed {
+        id: u64,
+        maze_seed: String,
+    },
+    
+    TournamentUpdated {
+        id: u64,
+        amendments: u32,
+    },
+    
+    TournamentsFinalized {
+        ids: Vec<u64>,
+    },
+    
+    TournamentsReconciled {
+        id: Option<u64>,                // Tournament whose register copy was merged
+        diverged: bool,                 // The two copies disagreed
+    },
+    
+    Migrated {
+        rewritten: u64,
+        schema_version: u32,            // Unchanged until the last batch completes
+        done: bool,
+    },
+    
+    HubMigrated {
+        hub_chain_id: String,
+        epoch: u32,
+        notified: u32,                  // User chains sent SetHub
+    },
+    
+    ShardsUpdated {
+        shards: Vec<String>,
+    },
+    
+    TemplateCreated {
+        id: u64,
+    },
+    
+    TournamentsRotated {
+        ended: Vec<u64>,
+        started: Vec<u64>,
+    },
+    
+    TournamentCancelled {
+        id: u64,
+        xp_reverted: u64,
+    },
+    
     TournamentEnded {
         id: u64,
         winner_count: u32,
@@ -273,6 +2072,28 @@ pub enum Response {
         xp_amount: u64,
     },
     
+    SeasonCreated {
+        id: u64,
+        end_time: Timestamp,
+        carried_over: u32,
+    },
+
+    SeasonEnded {
+        id: u64,
+        winner_count: u32,
+    },
+
+    SeasonRewardClaimed {
+        season_id: u64,
+        xp_amount: u64,
+    },
+
+    DuelResolved {
+        id: u64,
+        status: DuelStatus,
+        winner: Option<[u8; 20]>,
+    },
+
     TournamentBootstrapped {
         id: u64,
         end_time: Timestamp,
@@ -310,3 +2131,425 @@ impl ServiceAbi for LabyrinthTournamentContractAbi {
     type Query = async_graphql::Request;
     type QueryResponse = async_graphql::Response;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(stage: u32, time_ms: u64) -> StageSplit {
+        StageSplit { stage, time_ms, deaths: 1, coins: 2 }
+    }
+
+    #[test]
+    fn splits_must_add_up_to_the_run() {
+        let splits = [split(1, 1_000), split(2, 2_000), split(3, 3_000)];
+        assert!(validate_splits(&splits, 6_000, 3, 6).is_ok());
+        assert!(validate_splits(&[], 6_000, 3, 6).is_ok());
+
+        // One millisecond, death or coin off is rejected
+        assert!(validate_splits(&splits, 6_001, 3, 6).is_err());
+        assert!(validate_splits(&splits, 5_999, 3, 6).is_err());
+        assert!(validate_splits(&splits, 6_000, 4, 6).is_err());
+        assert!(validate_splits(&splits, 6_000, 3, 5).is_err());
+    }
+
+    #[test]
+    fn splits_are_numbered_from_one() {
+        assert!(validate_splits(&[split(0, 1_000)], 1_000, 1, 2).is_err());
+        assert!(validate_splits(&[split(2, 1_000)], 1_000, 1, 2).is_err());
+        assert!(validate_splits(&[split(1, 500), split(3, 500)], 1_000, 2, 4).is_err());
+        assert!(validate_splits(&[split(2, 500), split(1, 500)], 1_000, 2, 4).is_err());
+
+        let too_many: Vec<_> = (1..=MAX_STAGES as u32 + 1).map(|stage| split(stage, 100)).collect();
+        let count = too_many.len() as u32;
+        assert!(validate_splits(&too_many, 100 * count as u64, count, 2 * count).is_err());
+    }
+
+    #[test]
+    fn unfinished_last_stage_is_not_cleared() {
+        let splits = [split(1, 1_000), split(2, 2_000), split(3, 3_000)];
+        assert_eq!(cleared_splits(&splits, true).len(), 3);
+        assert_eq!(cleared_splits(&splits, false).len(), 2);
+        assert_eq!(cleared_splits(&splits, false).last().map(|s| s.stage), Some(2));
+
+        // A run that died in the first stage clears nothing
+        assert!(cleared_splits(&splits[..1], false).is_empty());
+        assert!(cleared_splits(&[], false).is_empty());
+        assert!(cleared_splits(&[], true).is_empty());
+    }
+
+    #[test]
+    fn partial_runs_fit_the_maze() {
+        let maze = Difficulty::Medium.default_maze_config();
+        let stages = maze.stages;
+        let splits: Vec<_> = (1..=stages).map(|stage| split(stage, 1_000)).collect();
+
+        assert!(maze.check_run(4_000, 8, true, &splits).is_ok());
+        // A partial run may stop at any stage, a completed one must report them all
+        assert!(maze.check_run(2_000, 4, false, &splits[..2]).is_ok());
+        assert!(maze.check_run(3_000, 6, true, &splits[..3]).is_err());
+        // Unsplit clients are still accepted
+        assert!(maze.check_run(4_000, 8, true, &[]).is_ok());
+
+        let mut extra = splits.clone();
+        extra.push(split(stages + 1, 1_000));
+        assert!(maze.check_run(5_000, 10, false, &extra).is_err());
+    }
+
+    #[test]
+    fn faster_player_gains_rating() {
+        let new = (DEFAULT_RATING, DEFAULT_RATING_DEVIATION);
+        let updated = glicko_update(&[(new.0, new.1, 40_000), (new.0, new.1, 50_000)]);
+        let (winner, loser) = (updated[0], updated[1]);
+
+        assert!(winner.0 > DEFAULT_RATING);
+        assert!(loser.0 < DEFAULT_RATING);
+        // Equal priors move by the same amount in opposite directions
+        assert!((winner.0 + loser.0).abs_diff(2 * DEFAULT_RATING) <= 1);
+        assert!(winner.1 < DEFAULT_RATING_DEVIATION && loser.1 < DEFAULT_RATING_DEVIATION);
+    }
+
+    #[test]
+    fn draws_and_lone_players_keep_their_rating() {
+        let updated = glicko_update(&[(1500, 200, 45_000), (1500, 200, 45_000)]);
+        assert_eq!(updated[0].0, 1500);
+        assert_eq!(updated[1].0, 1500);
+
+        assert_eq!(glicko_update(&[(1620, 80, 45_000)]), vec![(1620, 80)]);
+        assert!(glicko_update(&[]).is_empty());
+    }
+
+    #[test]
+    fn upsets_move_ratings_more() {
+        // The same win is worth more against a stronger opponent
+        let upset = glicko_update(&[(1400, 100, 40_000), (1700, 100, 50_000)]);
+        let expected = glicko_update(&[(1700, 100, 40_000), (1400, 100, 50_000)]);
+        assert!(upset[0].0 - 1400 > expected[0].0 - 1700);
+        assert!(1700 - upset[1].0 > 1400 - expected[1].0);
+    }
+
+    #[test]
+    fn rating_deviation_stays_in_bounds() {
+        // Many opponents shrink the deviation, but never below the floor
+        let field: Vec<_> = (0..50).map(|i| (1500, MIN_RATING_DEVIATION, 40_000 + i)).collect();
+        let updated = glicko_update(&field);
+        assert_eq!(updated.len(), field.len());
+        assert!(updated.iter().all(|&(_, rd)| (MIN_RATING_DEVIATION..=DEFAULT_RATING_DEVIATION).contains(&rd)));
+        // Standings order is kept: the fastest gains most
+        assert!(updated.windows(2).all(|pair| pair[0].0 >= pair[1].0));
+    }
+
+    fn template(recurrence: Recurrence, next_start: u64) -> TournamentTemplate {
+        TournamentTemplate {
+            id: 1,
+            title_pattern: "Cup #{n}".to_string(),
+            description: String::new(),
+            difficulty: Difficulty::Medium,
+            maze_config: Difficulty::Medium.default_maze_config(),
+            duration_hours: 24,
+            xp_reward_pool: 1_000,
+            reward_percentages: DEFAULT_REWARD_PERCENTAGES.to_vec(),
+            recurrence,
+            seed_mode: SeedMode::Shared,
+            team_mode: TeamMode::Individual,
+            team_best_n: 0,
+            active: true,
+            instances_created: 0,
+            current_tournament_id: None,
+            next_start: Timestamp::from(next_start),
+            created_at: Timestamp::from(0),
+        }
+    }
+
+    #[test]
+    fn next_slot_is_strictly_after_now() {
+        let day = Recurrence::Daily.period_micros();
+        let start = 10 * day;
+        let daily = template(Recurrence::Daily, start);
+        let slot = |now: u64| daily.next_slot_after(Timestamp::from(now)).micros();
+
+        assert_eq!(slot(start - 1), start);
+        assert_eq!(slot(start), start + day);
+        assert_eq!(slot(start + 1), start + day);
+        assert_eq!(slot(start + day - 1), start + day);
+        assert_eq!(slot(start + 2 * day), start + 3 * day);
+        // Missed periods are skipped, staying on the template's grid
+        assert_eq!(slot(start + 30 * day + 5), start + 31 * day);
+
+        let monthly = template(Recurrence::Monthly, start);
+        assert_eq!(monthly.next_slot_after(Timestamp::from(start)).micros(), start + 30 * day);
+        assert_eq!(Recurrence::SemiMonthly.period_micros(), 15 * day);
+    }
+
+    #[test]
+    fn reward_curves_are_bounded() {
+        assert!(validate_reward_curve(&DEFAULT_REWARD_PERCENTAGES).is_ok());
+        assert!(validate_reward_curve(&[100]).is_ok());
+        assert!(validate_reward_curve(&[50, 50]).is_ok());
+        assert!(validate_reward_curve(&[10; MAX_REWARD_SLOTS]).is_ok());
+
+        assert!(validate_reward_curve(&[]).is_err());
+        assert!(validate_reward_curve(&[50, 51]).is_err());
+        assert!(validate_reward_curve(&[1; MAX_REWARD_SLOTS + 1]).is_err());
+    }
+
+    fn entry(wallet: u8, rank: u32, best_time_ms: u64) -> LeaderboardEntry {
+        LeaderboardEntry {
+            rank,
+            wallet_address: [wallet; 20],
+            username: format!("player{}", wallet),
+            best_time_ms,
+            best_score: 0,
+            total_runs: 1,
+            total_xp: 10,
+        }
+    }
+
+    fn ranked(times: &[(u8, u64)]) -> Vec<LeaderboardEntry> {
+        times.iter().enumerate().map(|(i, &(wallet, time))| entry(wallet, i as u32 + 1, time)).collect()
+    }
+
+    #[test]
+    fn unchanged_board_has_no_changes() {
+        let board = ranked(&[(1, 100), (2, 200)]);
+        let (changes, removed) = leaderboard_changes(&board, &board);
+        assert!(changes.is_empty());
+        assert!(removed.is_empty());
+    }
+
+    #[test]
+    fn changes_carry_previous_ranks() {
+        let old = ranked(&[(1, 100), (2, 200), (3, 300)]);
+        let new = ranked(&[(1, 100), (3, 150), (2, 200), (4, 400)]);
+        let (changes, removed) = leaderboard_changes(&old, &new);
+
+        // Player 1 is untouched; 3 moved up, 2 moved down, 4 is new
+        let moved: Vec<_> = changes.iter().map(|c| (c.entry.wallet_address[0], c.previous_rank)).collect();
+        assert_eq!(moved, vec![(3, Some(3)), (2, Some(2)), (4, None)]);
+        assert!(removed.is_empty());
+        // Everyone here was already in the top slots, except the newcomer
+        assert_eq!(changes.iter().filter(|c| c.new_in_top).count(), 1);
+    }
+
+    #[test]
+    fn entering_the_top_is_flagged_once() {
+        let slots = LEADERBOARD_TOP_SLOTS as u8;
+        let old: Vec<_> = (1..=slots + 1).map(|wallet| entry(wallet, wallet as u32, wallet as u64 * 100)).collect();
+        // The player just outside the top overtakes the last one inside it
+        let mut new = old.clone();
+        new[slots as usize - 1].rank = LEADERBOARD_TOP_SLOTS + 1;
+        new[slots as usize].rank = LEADERBOARD_TOP_SLOTS;
+        new.swap(slots as usize - 1, slots as usize);
+
+        let (changes, _) = leaderboard_changes(&old, &new);
+        assert_eq!(changes.len(), 2);
+        let entered: Vec<_> = changes.iter().filter(|c| c.new_in_top).map(|c| c.entry.wallet_address[0]).collect();
+        assert_eq!(entered, vec![slots + 1]);
+    }
+
+    #[test]
+    fn applied_changes_rebuild_the_board() {
+        let old = ranked(&[(1, 100), (2, 200), (3, 300)]);
+        let new = ranked(&[(3, 90), (1, 100), (4, 250)]);
+        let (changes, removed) = leaderboard_changes(&old, &new);
+        assert_eq!(removed, vec![[2; 20]]);
+
+        let mut cached = old.clone();
+        apply_leaderboard_changes(&mut cached, changes, &removed);
+        assert_eq!(cached, new);
+
+        // A reset starts from an empty copy
+        let (changes, removed) = leaderboard_changes(&[], &new);
+        let mut fresh = Vec::new();
+        apply_leaderboard_changes(&mut fresh, changes, &removed);
+        assert_eq!(fresh, new);
+    }
+
+    #[test]
+    fn applied_board_keeps_the_top_hundred() {
+        let full: Vec<_> = (0..100u8).map(|i| entry(i, i as u32 + 1, 1_000 + i as u64)).collect();
+        let mut cached = full.clone();
+        apply_leaderboard_changes(&mut cached, vec![LeaderboardChange {
+            entry: entry(200, 101, 5_000),
+            previous_rank: None,
+            new_in_top: false,
+        }], &[]);
+        assert_eq!(cached, full);
+    }
+
+    #[test]
+    fn placements_count_beyond_a_short_reward_curve() {
+        // A one-slot curve only pays the winner; the podium still counts for ranks 2-3
+        let curve = [100];
+        assert!(validate_reward_curve(&curve).is_ok());
+        let mut board = ranked(&[(1, 100), (2, 200), (3, 300), (4, 400)]);
+        let placements = placement_achievements(&board);
+
+        let podium: Vec<_> = placements.iter()
+            .filter(|(_, id)| *id == AchievementId::Podium)
+            .map(|(wallet, _)| wallet[0])
+            .collect();
+        assert_eq!(podium, vec![1, 2, 3]);
+        let champions: Vec<_> = placements.iter().filter(|(_, id)| *id == AchievementId::Champion).collect();
+        assert_eq!(champions, vec![&([1; 20], AchievementId::Champion)]);
+        assert!(board.len() > curve.len());
+
+        // Players who never finished the maze don't place
+        board[2].best_time_ms = u64::MAX;
+        assert!(!placement_achievements(&board).contains(&([3; 20], AchievementId::Podium)));
+    }
+
+    fn ticket(tournament_id: u64, issued_at: u64) -> RunTicket {
+        RunTicket {
+            id: 1,
+            tournament_id,
+            wallet_address: [1; 20],
+            nonce: 7,
+            issued_at: Timestamp::from(issued_at),
+            entropy: [0; 32],
+            attempt_seed: String::new(),
+        }
+    }
+
+    #[test]
+    fn ticket_admits_runs_that_fit_since_issue() {
+        let ticket = ticket(3, 1_000_000);
+        let now = Timestamp::from(1_000_000 + 60_000_000);
+
+        assert!(ticket.validate(3, 60_000, now).is_ok());
+        assert!(ticket.validate(3, 30_000, now).is_ok());
+        assert_eq!(ticket.validate(4, 30_000, now), Err("Run ticket is for another tournament".to_string()));
+    }
+
+    #[test]
+    fn ticket_rejects_expired_and_too_fast_runs() {
+        let ticket = ticket(3, 1_000_000);
+
+        let expired = Timestamp::from(1_000_000 + RUN_TICKET_TTL_MICROS + 1);
+        assert!(ticket.expired(expired));
+        assert_eq!(ticket.validate(3, 30_000, expired), Err("Run ticket expired".to_string()));
+        // Exactly at the TTL the ticket still admits a run
+        assert!(ticket.validate(3, 30_000, Timestamp::from(1_000_000 + RUN_TICKET_TTL_MICROS)).is_ok());
+
+        // Claiming more time than has passed since issue
+        let now = Timestamp::from(1_000_000 + 60_000_000);
+        assert_eq!(
+            ticket.validate(3, 60_001, now),
+            Err("Claimed time exceeds time since ticket issue".to_string()),
+        );
+    }
+
+    fn reveal(moves: &[u8]) -> RunReveal {
+        RunReveal { moves: moves.to_vec(), salt: b"salt".to_vec() }
+    }
+
+    fn commitment(seed: &str, reveal: &RunReveal, committed_at: u64) -> RunCommitment {
+        RunCommitment {
+            tournament_id: 3,
+            wallet_address: [1; 20],
+            commitment: compute_run_commitment(seed, &reveal.moves, &reveal.salt),
+            committed_at: Timestamp::from(committed_at),
+        }
+    }
+
+    #[test]
+    fn reveal_must_match_the_commitment() {
+        let committed = commitment("seed-a", &reveal(b"udlr"), 1_000_000);
+        let now = Timestamp::from(1_000_000 + 60_000_000);
+
+        assert!(committed.verify("seed-a", &reveal(b"udlr"), 60_000, now).is_ok());
+        // Another trace, salt or maze seed changes the hash
+        let mismatch = Err("Reveal does not match commitment".to_string());
+        assert_eq!(committed.verify("seed-a", &reveal(b"udll"), 60_000, now), mismatch);
+        assert_eq!(committed.verify("seed-b", &reveal(b"udlr"), 60_000, now), mismatch);
+        let resalted = RunReveal { salt: b"pepper".to_vec(), ..reveal(b"udlr") };
+        assert_eq!(committed.verify("seed-a", &resalted, 60_000, now), mismatch);
+    }
+
+    #[test]
+    fn reveal_rejects_expired_window_and_too_fast_runs() {
+        let committed = commitment("seed-a", &reveal(b"udlr"), 1_000_000);
+
+        let expired = Timestamp::from(1_000_000 + REVEAL_WINDOW_MICROS + 1);
+        assert_eq!(
+            committed.verify("seed-a", &reveal(b"udlr"), 60_000, expired),
+            Err("Reveal window expired".to_string()),
+        );
+
+        let now = Timestamp::from(1_000_000 + 60_000_000);
+        assert_eq!(
+            committed.verify("seed-a", &reveal(b"udlr"), 60_001, now),
+            Err("Claimed time exceeds time since commit".to_string()),
+        );
+    }
+
+    fn duel_run(xp_earned: u64, time_ms: u64, completed: bool) -> DuelRun {
+        DuelRun {
+            time_ms,
+            score: 0,
+            coins: 0,
+            deaths: 0,
+            completed,
+            xp_earned,
+            submitted_at: Timestamp::from(0),
+        }
+    }
+
+    fn duel(challenger_run: Option<DuelRun>, opponent_run: Option<DuelRun>) -> Duel {
+        Duel {
+            id: 1,
+            challenger: [1; 20],
+            challenger_username: "alice".to_string(),
+            opponent: Some([2; 20]),
+            opponent_username: Some("bob".to_string()),
+            maze_seed: String::new(),
+            difficulty: Difficulty::Medium,
+            status: DuelStatus::Accepted,
+            challenger_run,
+            opponent_run,
+            winner: None,
+            created_at: Timestamp::from(0),
+            deadline: Timestamp::from(0),
+        }
+    }
+
+    #[test]
+    fn higher_xp_wins_the_duel() {
+        // XP decides before time, even against a faster run
+        let won = duel(Some(duel_run(300, 50_000, true)), Some(duel_run(250, 40_000, true)));
+        assert_eq!(won.decide_winner(), Some([1; 20]));
+        let lost = duel(Some(duel_run(250, 40_000, true)), Some(duel_run(300, 50_000, true)));
+        assert_eq!(lost.decide_winner(), Some([2; 20]));
+    }
+
+    #[test]
+    fn equal_xp_falls_back_to_completed_time() {
+        let faster = duel(Some(duel_run(300, 45_000, true)), Some(duel_run(300, 40_000, true)));
+        assert_eq!(faster.decide_winner(), Some([2; 20]));
+
+        // Times only count when both players finished
+        let unfinished = duel(Some(duel_run(300, 40_000, false)), Some(duel_run(300, 45_000, true)));
+        assert_eq!(unfinished.decide_winner(), None);
+    }
+
+    #[test]
+    fn missing_run_forfeits_and_ties_draw() {
+        assert_eq!(duel(Some(duel_run(100, 60_000, true)), None).decide_winner(), Some([1; 20]));
+        assert_eq!(duel(None, Some(duel_run(100, 60_000, true))).decide_winner(), Some([2; 20]));
+        assert_eq!(duel(None, None).decide_winner(), None);
+
+        let tied = duel(Some(duel_run(300, 45_000, true)), Some(duel_run(300, 45_000, true)));
+        assert_eq!(tied.decide_winner(), None);
+    }
+
+    #[test]
+    fn team_score_sums_the_best_members() {
+        let scores = vec![300, 900, 100, 500];
+        assert_eq!(TeamMode::TeamSum.team_score(scores.clone(), 2), (1_800, 4));
+        // Best-N keeps the top scores whatever order members come in
+        assert_eq!(TeamMode::TeamBestN.team_score(scores.clone(), 2), (1_400, 2));
+        assert_eq!(TeamMode::TeamBestN.team_score(scores.clone(), 10), (1_800, 4));
+        assert_eq!(TeamMode::TeamBestN.team_score(scores, 0), (0, 0));
+        assert_eq!(TeamMode::TeamSum.team_score(Vec::new(), 3), (0, 0));
+    }
+}
@@ -0,0 +1,145 @@
+//! Labyrinth Legends - Maze Generator (port of game-engine/src/maze.js)
+//! Must stay bit-for-bit identical to the JS engine so the contract can
+//! measure the same maze the client renders.
+//!
+//! The JS engine works in IEEE doubles; the LCG below does the same so
+//! rounding matches (a * state overflows 2^53 and is rounded in JS too).
+
+use std::collections::VecDeque;
+
+const NORTH: u8 = 1;
+const SOUTH: u8 = 2;
+const EAST: u8 = 4;
+const WEST: u8 = 8;
+
+/// (dx, dy, wall, opposite) in the JS engine's declaration order
+const DIRECTIONS: [(i32, i32, u8, u8); 4] = [
+    (0, -1, NORTH, SOUTH),
+    (0, 1, SOUTH, NORTH),
+    (1, 0, EAST, WEST),
+    (-1, 0, WEST, EAST),
+];
+
+/// Seeded LCG matching `SeededRandom` in maze.js
+struct SeededRandom {
+    state: f64,
+}
+
+impl SeededRandom {
+    const M: f64 = 2_147_483_648.0; // 0x80000000
+    const A: f64 = 1_103_515_245.0;
+    const C: f64 = 12_345.0;
+
+    fn new(seed: u32) -> Self {
+        SeededRandom { state: seed as f64 }
+    }
+
+    fn next(&mut self) -> f64 {
+        self.state = (Self::A * self.state + Self::C) % Self::M;
+        self.state / (Self::M - 1.0)
+    }
+
+    fn next_int(&mut self, min: usize, max: usize) -> usize {
+        (self.next() * (max - min + 1) as f64).floor() as usize + min
+    }
+}
+
+/// String hash matching `hashString` in maze.js (Java-style, int32 wrap, abs)
+pub fn hash_seed(seed: &str) -> u32 {
+    let mut hash: i32 = 0;
+    for unit in seed.encode_utf16() {
+        let shifted = hash.wrapping_shl(5) as i64;
+        hash = (shifted - hash as i64 + unit as i64) as i32;
+    }
+    hash.unsigned_abs()
+}
+
+/// Generated maze: wall bitmask per cell (row-major)
+pub struct Maze {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<u8>,
+}
+
+impl Maze {
+    /// Generate with recursive backtracking, same RNG call order as the JS engine
+    pub fn generate(seed: &str, width: usize, height: usize) -> Self {
+        let mut rng = SeededRandom::new(hash_seed(seed));
+        let mut cells = vec![NORTH | SOUTH | EAST | WEST; width * height];
+        let mut visited = vec![false; width * height];
+
+        let start_x = rng.next_int(0, width - 1);
+        let start_y = rng.next_int(0, height - 1);
+
+        // Explicit stack instead of recursion: (x, y, shuffled directions, next index)
+        let mut stack = vec![(start_x, start_y, shuffle(&mut rng), 0usize)];
+        visited[start_y * width + start_x] = true;
+
+        while let Some(frame) = stack.last_mut() {
+            let (x, y) = (frame.0, frame.1);
+            if frame.3 == DIRECTIONS.len() {
+                stack.pop();
+                continue;
+            }
+            let (dx, dy, wall, opposite) = DIRECTIONS[frame.2[frame.3]];
+            frame.3 += 1;
+
+            let nx = x as i32 + dx;
+            let ny = y as i32 + dy;
+            if nx < 0 || ny < 0 || nx >= width as i32 || ny >= height as i32 {
+                continue;
+            }
+            let (nx, ny) = (nx as usize, ny as usize);
+            if visited[ny * width + nx] {
+                continue;
+            }
+
+            cells[y * width + x] &= !wall;
+            cells[ny * width + nx] &= !opposite;
+            visited[ny * width + nx] = true;
+            // The JS engine shuffles on entering the next cell
+            let directions = shuffle(&mut rng);
+            stack.push((nx, ny, directions, 0));
+        }
+
+        Maze { width, height, cells }
+    }
+
+    /// Moves on the shortest path from start (0, 0) to exit (width-1, height-1)
+    pub fn shortest_path_length(&self) -> u32 {
+        let target = self.width * self.height - 1;
+        let mut distance = vec![u32::MAX; self.width * self.height];
+        let mut queue = VecDeque::from([0usize]);
+        distance[0] = 0;
+
+        while let Some(index) = queue.pop_front() {
+            if index == target {
+                return distance[index];
+            }
+            let (x, y) = ((index % self.width) as i32, (index / self.width) as i32);
+            for (dx, dy, wall, _) in DIRECTIONS {
+                if self.cells[index] & wall != 0 {
+                    continue;
+                }
+                let next = (y + dy) as usize * self.width + (x + dx) as usize;
+                if distance[next] == u32::MAX {
+                    distance[next] = distance[index] + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        // Unreachable for a perfect maze
+        0
+    }
+}
+
+/// Fisher-Yates shuffle of direction indices, matching `shuffle` in maze.js
+fn shuffle(rng: &mut SeededRandom) -> [usize; 4] {
+    let mut order = [0, 1, 2, 3];
+    for i in (1..order.len()).rev() {
+        let j = rng.next_int(0, i);
+        order.swap(i, j);
+    }
+    order
+}
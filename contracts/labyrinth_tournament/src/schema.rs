@@ -0,0 +1,313 @@
+//! Labyrinth Legends - Storage Schema Versions
+//! Records are BCS-encoded straight into views, and BCS has no field tags, so a
+//! record written before a field was appended no longer decodes as the new struct.
+//!
+//! Versioned tables (tournaments, players, tournament_players, runs) store
+//! `Versioned<T>` values: the record behind a tag naming its layout version.
+//! Values written before versioning carry no tag and decode as version 1.
+//! Reads upgrade older layouts; writes always use the current one, so a record
+//! is upgraded in storage the next time it is saved (or by the batch `Migrate`
+//! operation). The records themselves keep plain derived serde, so they nest in
+//! messages and other values like any other type.
+//!
+//! VERSION HISTORY:
+//! 1. Baseline layout (no version register, untagged values)
+//! 2. Appended fields for seasons, ratings, duels, clans, splits, ghosts,
+//!    commit-reveal, run tickets, per-attempt seeds, seed commitments, maze
+//!    config, cancellation, reward curves and templates; values are tagged
+
+use serde::{de, de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    Difficulty, GameRun, Player, SeedMode, TeamMode, Tournament, TournamentPlayer,
+    TournamentStatus, DEFAULT_RATING, DEFAULT_RATING_DEVIATION, DEFAULT_REWARD_PERCENTAGES,
+    maze_path_length,
+};
+use linera_sdk::linera_base_types::Timestamp;
+
+/// Schema version written by this code
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// Batch migration progress (see Operation::Migrate)
+#[derive(Clone, Debug, Default, Deserialize, Serialize, async_graphql::SimpleObject)]
+pub struct MigrationCursor {
+    pub table: u32,                     // Index into MIGRATION_TABLES
+    pub last_key: Option<Vec<u8>>,      // BCS key of the last rewritten entry of that table
+}
+
+/// Tables rewritten by Migrate, in order
+pub const MIGRATION_TABLES: [&str; 4] = ["tournaments", "players", "tournament_players", "runs"];
+
+/// Leads every tagged value, ahead of its version (untagged values predate versioning)
+pub const RECORD_TAG: [u8; 4] = *b"LLrv";
+
+/// A record type stored in a versioned table
+pub trait VersionedRecord: Serialize + DeserializeOwned {
+    /// Schema version 1 layout, stored untagged
+    type V1: DeserializeOwned + Into<Self>;
+}
+
+impl VersionedRecord for Tournament {
+    type V1 = v1::Tournament;
+}
+
+impl VersionedRecord for Player {
+    type V1 = v1::Player;
+}
+
+impl VersionedRecord for TournamentPlayer {
+    type V1 = v1::TournamentPlayer;
+}
+
+impl VersionedRecord for GameRun {
+    type V1 = v1::GameRun;
+}
+
+/// Storage value of a versioned table: the record and the layout it was read from
+/// Untagged values carry no length, so a `Versioned` decodes the rest of its input:
+/// it must be the whole stored value (or its last field), never followed by other data
+#[derive(Clone, Debug)]
+pub struct Versioned<T> {
+    record: T,
+    version: u32,
+}
+
+impl<T> Versioned<T> {
+    /// Wrap a record for writing (always stored in the current layout)
+    pub fn new(record: T) -> Self {
+        Versioned { record, version: CURRENT_SCHEMA_VERSION }
+    }
+
+    pub fn get(&self) -> &T {
+        &self.record
+    }
+
+    pub fn into_inner(self) -> T {
+        self.record
+    }
+
+    /// Layout version the value was stored in
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+impl<T: VersionedRecord> Versioned<T> {
+    /// Decode a stored value, tagged or untagged; all input must be consumed
+    pub fn decode(bytes: &[u8]) -> Result<Self, bcs::Error> {
+        if let Some(tagged) = bytes.strip_prefix(&RECORD_TAG) {
+            // A legacy value may start with the tag bytes by chance: fall back below
+            if let Ok((version, record)) = bcs::from_bytes::<(u32, T)>(tagged) {
+                if version == CURRENT_SCHEMA_VERSION {
+                    return Ok(Versioned { record, version });
+                }
+            }
+        }
+        let legacy = bcs::from_bytes::<T::V1>(bytes)?;
+        Ok(Versioned { record: legacy.into(), version: 1 })
+    }
+}
+
+impl<T: Serialize> Serialize for Versioned<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (RECORD_TAG, CURRENT_SCHEMA_VERSION, &self.record).serialize(serializer)
+    }
+}
+
+impl<'de, T: VersionedRecord> Deserialize<'de> for Versioned<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Untagged values have no length prefix, so take the rest of the stored bytes
+        let bytes = deserializer.deserialize_tuple(usize::MAX, RemainingBytes)?;
+        Versioned::decode(&bytes).map_err(de::Error::custom)
+    }
+}
+
+/// Collects whatever input is left (versioned values are always stored last)
+struct RemainingBytes;
+
+impl<'de> de::Visitor<'de> for RemainingBytes {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("the remaining bytes of a stored record")
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::new();
+        loop {
+            match seq.next_element::<u8>() {
+                Ok(Some(byte)) => bytes.push(byte),
+                Ok(None) => break,
+                // BCS reports the end of input as an error: that is the end of the value
+                Err(error) if error.to_string() == bcs::Error::Eof.to_string() => break,
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+/// Schema version 1 layouts (frozen - never edit)
+pub mod v1 {
+    use super::*;
+
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct Tournament {
+        pub id: u64,
+        pub title: String,
+        pub description: String,
+        pub maze_seed: String,
+        pub difficulty: Difficulty,
+        pub start_time: Timestamp,
+        pub end_time: Timestamp,
+        pub status: TournamentStatus,
+        pub participant_count: u32,
+        pub total_runs: u64,
+        pub xp_reward_pool: u64,
+        pub created_at: Timestamp,
+    }
+
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct TournamentPlayer {
+        pub wallet_address: [u8; 20],
+        pub username: String,
+        pub best_time_ms: u64,
+        pub best_score: u64,
+        pub total_runs: u32,
+        pub total_xp_earned: u64,
+        pub last_run_at: Timestamp,
+        pub joined_at: Timestamp,
+    }
+
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct Player {
+        pub wallet_address: [u8; 20],
+        pub username: String,
+        pub total_xp: u64,
+        pub total_runs: u64,
+        pub tournaments_played: u32,
+        pub tournaments_won: u32,
+        pub best_time_ms: Option<u64>,
+        pub registered_at: Timestamp,
+        pub last_active: Timestamp,
+    }
+
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct GameRun {
+        pub id: u64,
+        pub tournament_id: u64,
+        pub wallet_address: [u8; 20],
+        pub username: String,
+        pub time_ms: u64,
+        pub score: u64,
+        pub coins: u32,
+        pub deaths: u32,
+        pub completed: bool,
+        pub xp_earned: u64,
+        pub created_at: Timestamp,
+    }
+
+    // Legacy seeds were timestamp strings, so seed_entropy stays zeroed and
+    // verify_seed() reports them as unverifiable
+    impl From<Tournament> for crate::Tournament {
+        fn from(old: Tournament) -> Self {
+            let maze_config = old.difficulty.default_maze_config();
+            crate::Tournament {
+                reference_path_length: maze_path_length(&old.maze_seed, &maze_config),
+                id: old.id,
+                title: old.title,
+                description: old.description,
+                maze_seed: old.maze_seed,
+                difficulty: old.difficulty,
+                start_time: old.start_time,
+                end_time: old.end_time,
+                status: old.status,
+                participant_count: old.participant_count,
+                total_runs: old.total_runs,
+                xp_reward_pool: old.xp_reward_pool,
+                created_at: old.created_at,
+                season_id: None,
+                team_mode: TeamMode::Individual,
+                team_best_n: 0,
+                commit_reveal_required: false,
+                seed_mode: SeedMode::Shared,
+                seed_commitment: None,
+                seed_entropy: [0; 32],
+                seed_secret: None,
+                seed_revealed: true,
+                maze_config,
+                organizer: None,
+                cancelled_at: None,
+                reward_percentages: DEFAULT_REWARD_PERCENTAGES.to_vec(),
+                template_id: None,
+            }
+        }
+    }
+
+    impl From<TournamentPlayer> for crate::TournamentPlayer {
+        fn from(old: TournamentPlayer) -> Self {
+            crate::TournamentPlayer {
+                wallet_address: old.wallet_address,
+                username: old.username,
+                best_time_ms: old.best_time_ms,
+                best_score: old.best_score,
+                total_runs: old.total_runs,
+                total_xp_earned: old.total_xp_earned,
+                last_run_at: old.last_run_at,
+                joined_at: old.joined_at,
+                best_splits: Vec::new(),
+                sum_of_best_ms: None,
+                ghost_run_id: None,
+            }
+        }
+    }
+
+    impl From<Player> for crate::Player {
+        fn from(old: Player) -> Self {
+            crate::Player {
+                wallet_address: old.wallet_address,
+                username: old.username,
+                total_xp: old.total_xp,
+                total_runs: old.total_runs,
+                tournaments_played: old.tournaments_played,
+                tournaments_won: old.tournaments_won,
+                best_time_ms: old.best_time_ms,
+                registered_at: old.registered_at,
+                last_active: old.last_active,
+                rating: DEFAULT_RATING,
+                rating_deviation: DEFAULT_RATING_DEVIATION,
+                rated_tournaments: 0,
+                rating_provisional: true,
+                duels_played: 0,
+                duels_won: 0,
+            }
+        }
+    }
+
+    // The seed a legacy run played is the tournament's; it is not recoverable here
+    impl From<GameRun> for crate::GameRun {
+        fn from(old: GameRun) -> Self {
+            crate::GameRun {
+                id: old.id,
+                tournament_id: old.tournament_id,
+                wallet_address: old.wallet_address,
+                username: old.username,
+                time_ms: old.time_ms,
+                score: old.score,
+                coins: old.coins,
+                deaths: old.deaths,
+                completed: old.completed,
+                xp_earned: old.xp_earned,
+                created_at: old.created_at,
+                splits: Vec::new(),
+                verified: false,
+                ticket_id: 0,
+                ticket_nonce: 0,
+                maze_seed: String::new(),
+                path_length: 0,
+                ranked_time_ms: old.time_ms,
+                ticket_entropy: [0; 32],
+            }
+        }
+    }
+}
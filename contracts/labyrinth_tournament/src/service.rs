@@ -8,10 +8,20 @@ mod state;
 use std::sync::Arc;
 
 use self::state::LabyrinthState;
-use async_graphql::{EmptySubscription, Object, Request, Response, Schema, SimpleObject};
+use async_graphql::{
+    futures_util::{future, stream, Stream, StreamExt},
+    Object, Request, Response, Schema, SimpleObject, Subscription,
+};
 use labyrinth_tournament::{
     Difficulty, Tournament, TournamentStatus, Player, TournamentPlayer,
     GameRun, LeaderboardEntry, TournamentReward, Operation, AccountOwner,
+    AchievementId, PlayerAchievement, ACHIEVEMENTS,
+    Season, SeasonStatus, SeasonStanding, SeasonReward, RatingEntry,
+    Duel, DuelStatus, Clan, ClanMember, ClanRole, TeamMode, TeamLeaderboardEntry,
+    SplitLeaderboardEntry, Ghost, RunCommitment, RunReveal,
+    RunTicket, RunReceipt, ReceiptStatus, RunSubmission, RunSubmissionInput, MAX_BATCH_RUNS, SeedMode, CancelXpPolicy, TournamentUpdate, TournamentAmendment, TournamentInput, DuelRunSubmission,
+    TemplateInput, TournamentTemplate, DEFAULT_REWARD_PERCENTAGES,
+    schema::{MigrationCursor, Versioned, CURRENT_SCHEMA_VERSION},
 };
 use linera_sdk::{
     abi::WithServiceAbi,
@@ -48,10 +58,12 @@ impl Service for LabyrinthTournamentService {
         let schema = Schema::build(
             QueryRoot { state: self.state.clone() },
             MutationRoot { runtime: self.runtime.clone() },
-            EmptySubscription,
+            SubscriptionRoot { state: self.state.clone() },
         )
         .finish();
-        schema.execute(query).await
+        // Queries and mutations answer once; subscriptions yield their single update
+        let response = schema.execute_stream(query).next().await;
+        response.unwrap_or_default()
     }
 }
 
@@ -76,6 +88,63 @@ struct Stats {
     active_tournament_id: Option<u64>,
 }
 
+/// Storage schema status
+#[derive(SimpleObject)]
+struct SchemaStatus {
+    stored: u32,
+    current: u32,
+    cursor: MigrationCursor,
+}
+
+/// Achievement catalogue entry
+#[derive(SimpleObject)]
+struct AchievementInfo {
+    id: AchievementId,
+    name: String,
+    description: String,
+}
+
+/// Matchmaking inputs for head-to-head modes
+/// The search window is rating +/- 2 * rating_deviation
+#[derive(SimpleObject)]
+struct MatchmakingProfile {
+    wallet_address: [u8; 20],
+    rating: u32,
+    rating_deviation: u32,
+    provisional: bool,
+    min_rating: u32,
+    max_rating: u32,
+}
+
+/// Seed-fairness proof for a run
+/// expected_seed is recomputed from the tournament seed and the run's ticket entropy
+#[derive(SimpleObject)]
+struct SeedProof {
+    run_id: u64,
+    tournament_seed: String,
+    seed_mode: SeedMode,
+    ticket_nonce: u64,
+    expected_seed: String,
+    run_seed: String,
+    valid: bool,
+    path_length: u32,
+    reference_path_length: u32,
+}
+
+impl MatchmakingProfile {
+    fn from_player(player: &Player) -> Self {
+        let window = player.rating_deviation * 2;
+        MatchmakingProfile {
+            wallet_address: player.wallet_address,
+            rating: player.rating,
+            rating_deviation: player.rating_deviation,
+            provisional: player.rating_provisional,
+            min_rating: player.rating.saturating_sub(window),
+            max_rating: player.rating.saturating_add(window),
+        }
+    }
+}
+
 #[Object]
 impl QueryRoot {
     // ===== Stats =====
@@ -95,33 +164,78 @@ impl QueryRoot {
         }
     }
 
+    /// Storage schema status: stored version, code version and batch progress
+    /// `stored < current` means Migrate has not finished yet
+    async fn schema_version(&self) -> SchemaStatus {
+        SchemaStatus {
+            stored: *self.state.schema_version.get(),
+            current: CURRENT_SCHEMA_VERSION,
+            cursor: self.state.migration_cursor.get().clone(),
+        }
+    }
+
+    /// Hub chain this chain routes to (None = never configured, the creator chain is used)
+    async fn hub_chain_id(&self) -> Option<String> {
+        self.state.hub_chain_id.get().clone()
+    }
+
+    /// Number of hub migrations this chain has seen
+    async fn hub_epoch(&self) -> u32 {
+        *self.state.hub_epoch.get()
+    }
+
+    /// Chain that accepts a tournament's runs and tickets (send them there)
+    /// The hub mirrors its counters and leaderboard; stage boards and ghosts stay there.
+    /// Exact on the hub; user chains answer from locateTournament lookups.
+    /// None means the hub, as given by hubChainId
+    async fn tournament_chain_id(&self, tournament_id: u64) -> Option<String> {
+        match self.state.tournament_shards.get(&tournament_id).await.ok().flatten() {
+            Some(chain_id) => Some(chain_id.to_string()),
+            None => self.state.hub_chain_id.get().clone(),
+        }
+    }
+
+    /// Shard chains taking new tournaments (hub only)
+    async fn shards(&self) -> Vec<String> {
+        self.state.shard_chains.get().iter().map(|chain_id| chain_id.to_string()).collect()
+    }
+
     // ===== Tournament Queries =====
 
-    /// Get the currently active tournament.
-    /// Returns from RegisterView for most up-to-date state after mutations.
+    /// Get the featured (active) tournament
     async fn active_tournament(&self) -> Option<Tournament> {
-        // CRITICAL: Read from RegisterView which has the latest mutated state
-        // This is the authoritative source after apply_run_on_hub mutations
-        if let Some(tournament) = self.state.active_tournament.get().clone() {
-            return Some(tournament);
-        }
-        
-        // Fallback to MapView if RegisterView is not set (shouldn't happen)
-        if let Some(id) = *self.state.active_tournament_id.get() {
-            if let Ok(Some(tournament)) = self.state.tournaments.get(&id).await {
-                return Some(tournament);
-            }
-        }
-        
-        // No tournament exists yet - return None
-        None
+        let id = (*self.state.active_tournament_id.get())?;
+        self.state.tournaments.get(&id).await.ok().flatten().map(Versioned::into_inner)
     }
 
     /// Get tournament by ID
     /// Returns only actual on-chain state - no fallback data.
     async fn tournament(&self, id: u64) -> Option<Tournament> {
         // Only return actual on-chain state
-        self.state.tournaments.get(&id).await.ok().flatten()
+        self.state.tournaments.get(&id).await.ok().flatten().map(Versioned::into_inner)
+    }
+
+    /// Get a tournament template by ID
+    async fn template(&self, id: u64) -> Option<TournamentTemplate> {
+        self.state.templates.get(&id).await.ok().flatten()
+    }
+
+    /// Get all tournament templates (optionally only active ones)
+    async fn templates(&self, active: Option<bool>) -> Vec<TournamentTemplate> {
+        let mut templates = Vec::new();
+        let _ = self.state.templates.for_each_index_value(|_, template| {
+            if active.is_none_or(|a| template.active == a) {
+                templates.push(template.into_owned());
+            }
+            Ok(())
+        }).await;
+        templates
+    }
+
+    /// Get a tournament's amendment log (oldest first)
+    async fn tournament_amendments(&self, tournament_id: u64) -> Vec<TournamentAmendment> {
+        self.state.tournament_amendments.get(&tournament_id).await.ok().flatten()
+            .unwrap_or_default()
     }
 
     /// Get all tournaments
@@ -131,6 +245,7 @@ impl QueryRoot {
 
         for id in 1..next_id {
             if let Ok(Some(tournament)) = self.state.tournaments.get(&id).await {
+                let tournament = tournament.into_inner();
                 if status.is_none() || status == Some(tournament.status) {
                     result.push(tournament);
                 }
@@ -153,28 +268,72 @@ impl QueryRoot {
         entries.into_iter().take(limit).collect()
     }
 
+    /// When the tournament's chain last pushed a leaderboard delta to this chain
+    /// None on the tournament's own chain, or if this chain never subscribed
+    async fn leaderboard_synced_at(&self, tournament_id: u64) -> Option<linera_sdk::linera_base_types::Timestamp> {
+        self.state.leaderboard_synced_at.get(&tournament_id).await.ok().flatten()
+    }
+
     /// Get player's rank in a tournament
     async fn player_rank(&self, tournament_id: u64, owner: String) -> Option<u32> {
         let wallet = parse_wallet_address(&owner)?;
-        let entries = self.state.leaderboards.get(&tournament_id).await.ok().flatten()?;
+        let entries = match self.state.leaderboards.get(&tournament_id).await.ok().flatten() {
+            Some(entries) => entries,
+            // User chains: rank cached from the hub's last snapshot
+            None => return self.state.cached_ranks.get(&(tournament_id, wallet)).await.ok().flatten(),
+        };
         
         entries.iter()
             .find(|e| e.wallet_address == wallet)
             .map(|e| e.rank)
     }
 
+    /// Get clan leaderboard for a team tournament (sorted by score)
+    async fn team_leaderboard(&self, tournament_id: u64, limit: Option<u32>) -> Vec<TeamLeaderboardEntry> {
+        let entries = self.state.team_leaderboards.get(&tournament_id).await.ok().flatten()
+            .unwrap_or_default();
+
+        let limit = limit.unwrap_or(100) as usize;
+        entries.into_iter().take(limit).collect()
+    }
+
+    /// Get best times for one stage of a tournament (stage numbers start at 1)
+    async fn stage_leaderboard(&self, tournament_id: u64, stage: u32, limit: Option<u32>) -> Vec<SplitLeaderboardEntry> {
+        let entries = self.state.stage_leaderboards.get(&(tournament_id, stage)).await.ok().flatten()
+            .unwrap_or_default();
+
+        let limit = limit.unwrap_or(100) as usize;
+        entries.into_iter().take(limit).collect()
+    }
+
+    /// Get sum-of-best-splits leaderboard for a tournament
+    async fn sum_of_best_leaderboard(&self, tournament_id: u64, limit: Option<u32>) -> Vec<SplitLeaderboardEntry> {
+        let entries = self.state.sum_of_best_leaderboards.get(&tournament_id).await.ok().flatten()
+            .unwrap_or_default();
+
+        let limit = limit.unwrap_or(100) as usize;
+        entries.into_iter().take(limit).collect()
+    }
+
     // ===== Player Queries =====
 
     /// Get player by wallet address (hex string, e.g., "0x...")
     async fn player(&self, owner: String) -> Option<Player> {
         let wallet = parse_wallet_address(&owner)?;
-        self.state.players.get(&wallet).await.ok().flatten()
+        self.state.players.get(&wallet).await.ok().flatten().map(Versioned::into_inner)
+    }
+
+    /// When the hub last pushed this player's snapshot to this chain
+    /// None on the hub, or if the chain never subscribed (its local profile may then be stale)
+    async fn player_synced_at(&self, owner: String) -> Option<linera_sdk::linera_base_types::Timestamp> {
+        let wallet = parse_wallet_address(&owner)?;
+        self.state.snapshot_synced_at.get(&wallet).await.ok().flatten()
     }
 
     /// Get player by username
     async fn player_by_username(&self, username: String) -> Option<Player> {
         let wallet = self.state.username_to_wallet.get(&username).await.ok().flatten()?;
-        self.state.players.get(&wallet).await.ok().flatten()
+        self.state.players.get(&wallet).await.ok().flatten().map(Versioned::into_inner)
     }
 
     /// Check if player is registered
@@ -197,14 +356,101 @@ impl QueryRoot {
     async fn tournament_player(&self, tournament_id: u64, owner: String) -> Option<TournamentPlayer> {
         let wallet = parse_wallet_address(&owner)?;
         let key = (tournament_id, wallet);
-        self.state.tournament_players.get(&key).await.ok().flatten()
+        self.state.tournament_players.get(&key).await.ok().flatten().map(Versioned::into_inner)
     }
 
     // ===== Run Queries =====
 
     /// Get run by ID
     async fn run(&self, id: u64) -> Option<GameRun> {
-        self.state.runs.get(&id).await.ok().flatten()
+        self.state.runs.get(&id).await.ok().flatten().map(Versioned::into_inner)
+    }
+
+    /// Get a run's ghost replay (only top-N personal bests are kept)
+    async fn ghost(&self, run_id: u64) -> Option<Ghost> {
+        self.state.ghosts.get(&run_id).await.ok().flatten()
+    }
+
+    /// Get the ghost of the best-ranked run in a tournament (race the #1 time)
+    async fn top_ghost(&self, tournament_id: u64) -> Option<Ghost> {
+        let ids = self.state.tournament_ghosts.get(&tournament_id).await.ok().flatten()?;
+
+        let mut best: Option<Ghost> = None;
+        for id in ids {
+            if let Ok(Some(ghost)) = self.state.ghosts.get(&id).await {
+                if best.as_ref().is_none_or(|b| ghost.ranked_time_ms < b.ranked_time_ms) {
+                    best = Some(ghost);
+                }
+            }
+        }
+        best
+    }
+
+    /// Get a player's pending run commitment for a tournament
+    async fn run_commitment(&self, tournament_id: u64, owner: String) -> Option<RunCommitment> {
+        let wallet = parse_wallet_address(&owner)?;
+        self.state.run_commitments.get(&(tournament_id, wallet)).await.ok().flatten()
+    }
+
+    /// Get a run ticket by the nonce the client chose in startRun
+    /// Its attempt_seed is the maze to render; used and expired tickets are gone
+    async fn run_ticket(&self, owner: String, nonce: u64) -> Option<RunTicket> {
+        let wallet = parse_wallet_address(&owner)?;
+        self.state.run_tickets.get(&(wallet, nonce)).await.ok().flatten()
+    }
+
+    /// Check a tournament's seed follows from its chain entropy and committed secret
+    /// None while the seed is still hidden
+    async fn verify_tournament_seed(&self, tournament_id: u64) -> Option<bool> {
+        let tournament = self.state.tournaments.get(&tournament_id).await.ok().flatten().map(Versioned::into_inner)?;
+        tournament.seed_revealed.then(|| tournament.verify_seed())
+    }
+
+    /// Recompute which seed a run used and check it matches the stored one
+    async fn verify_run_seed(&self, run_id: u64) -> Option<SeedProof> {
+        let run = self.state.runs.get(&run_id).await.ok().flatten().map(Versioned::into_inner)?;
+        let tournament = self.state.tournaments.get(&run.tournament_id).await.ok().flatten().map(Versioned::into_inner)?;
+
+        let expected_seed = tournament.attempt_seed(&run.ticket_entropy);
+        Some(SeedProof {
+            run_id,
+            tournament_seed: tournament.maze_seed,
+            seed_mode: tournament.seed_mode,
+            ticket_nonce: run.ticket_nonce,
+            valid: expected_seed == run.maze_seed,
+            expected_seed,
+            run_seed: run.maze_seed,
+            path_length: run.path_length,
+            reference_path_length: tournament.reference_path_length,
+        })
+    }
+
+    /// Runs submitted from THIS chain and the hub's answer (newest first)
+    /// Query the user chain, not the hub: receipts live where the run was submitted
+    async fn run_receipts(
+        &self,
+        owner: Option<String>,
+        status: Option<ReceiptStatus>,
+        limit: Option<u32>,
+    ) -> Vec<RunReceipt> {
+        let wallet = owner.as_deref().and_then(parse_wallet_address);
+        let mut receipts = Vec::new();
+        self.state.run_receipts.for_each_index_value(|_, receipt| {
+            if wallet.is_none_or(|w| receipt.wallet_address == w)
+                && status.is_none_or(|s| receipt.status == s)
+            {
+                receipts.push(receipt.into_owned());
+            }
+            Ok(())
+        }).await.unwrap_or_default();
+        receipts.sort_by_key(|r| std::cmp::Reverse(r.id));
+        receipts.truncate(limit.unwrap_or(50) as usize);
+        receipts
+    }
+
+    /// Get a delivery receipt by the ID submitRun returned
+    async fn run_receipt(&self, id: u64) -> Option<RunReceipt> {
+        self.state.run_receipts.get(&id).await.ok().flatten()
     }
 
     /// Get recent runs (activity feed)
@@ -215,7 +461,7 @@ impl QueryRoot {
         let mut runs = Vec::new();
         for id in run_ids.iter().take(limit) {
             if let Ok(Some(run)) = self.state.runs.get(id).await {
-                runs.push(run);
+                runs.push(run.into_inner());
             }
         }
         runs
@@ -249,6 +495,226 @@ impl QueryRoot {
 
         rewards
     }
+
+    // ===== Season Queries =====
+
+    /// Get the currently active season
+    async fn current_season(&self) -> Option<Season> {
+        let id = (*self.state.active_season_id.get())?;
+        self.state.seasons.get(&id).await.ok().flatten()
+    }
+
+    /// Get season by ID
+    async fn season(&self, id: u64) -> Option<Season> {
+        self.state.seasons.get(&id).await.ok().flatten()
+    }
+
+    /// Get all seasons (current and historical)
+    async fn seasons(&self, status: Option<SeasonStatus>) -> Vec<Season> {
+        let mut result = Vec::new();
+        let next_id = *self.state.next_season_id.get();
+
+        for id in 1..next_id {
+            if let Ok(Some(season)) = self.state.seasons.get(&id).await {
+                if status.is_none() || status == Some(season.status) {
+                    result.push(season);
+                }
+            }
+        }
+
+        // Newest first
+        result.reverse();
+        result
+    }
+
+    /// Get season leaderboard (sorted by points)
+    async fn season_leaderboard(&self, season_id: u64, limit: Option<u32>) -> Vec<SeasonStanding> {
+        let standings = self.state.season_standings.get(&season_id).await.ok().flatten()
+            .unwrap_or_default();
+
+        let limit = limit.unwrap_or(100) as usize;
+        standings.into_iter().take(limit).collect()
+    }
+
+    /// Get player's reward for a season
+    async fn season_reward(&self, season_id: u64, owner: String) -> Option<SeasonReward> {
+        let wallet = parse_wallet_address(&owner)?;
+        let key = (season_id, wallet);
+        self.state.season_rewards.get(&key).await.ok().flatten()
+    }
+
+    // ===== Rating Queries =====
+
+    /// Get global ratings leaderboard (sorted by rating)
+    async fn ratings_leaderboard(&self, limit: Option<u32>) -> Vec<RatingEntry> {
+        let limit = limit.unwrap_or(100) as usize;
+        self.state.rating_leaderboard.get().iter().take(limit).cloned().collect()
+    }
+
+    /// Get a player's matchmaking inputs (rating and search window)
+    async fn matchmaking_profile(&self, owner: String) -> Option<MatchmakingProfile> {
+        let wallet = parse_wallet_address(&owner)?;
+        let player = self.state.players.get(&wallet).await.ok().flatten().map(Versioned::into_inner)?;
+        Some(MatchmakingProfile::from_player(&player))
+    }
+
+    /// Get rated opponents inside a player's search window (closest rating first)
+    async fn matchmaking_candidates(&self, owner: String, limit: Option<u32>) -> Vec<RatingEntry> {
+        let wallet = match parse_wallet_address(&owner) {
+            Some(w) => w,
+            None => return Vec::new(),
+        };
+        let player = match self.state.players.get(&wallet).await.ok().flatten().map(Versioned::into_inner) {
+            Some(p) => p,
+            None => return Vec::new(),
+        };
+        let profile = MatchmakingProfile::from_player(&player);
+
+        let mut candidates: Vec<RatingEntry> = self.state.rating_leaderboard.get().iter()
+            .filter(|e| e.wallet_address != wallet)
+            .filter(|e| e.rating >= profile.min_rating && e.rating <= profile.max_rating)
+            .cloned()
+            .collect();
+        candidates.sort_by_key(|e| e.rating.abs_diff(player.rating));
+
+        let limit = limit.unwrap_or(10) as usize;
+        candidates.truncate(limit);
+        candidates
+    }
+
+    // ===== Clan Queries =====
+
+    /// Get clan by ID
+    async fn clan(&self, id: u64) -> Option<Clan> {
+        self.state.clans.get(&id).await.ok().flatten()
+    }
+
+    /// Get clan by name (case-insensitive)
+    async fn clan_by_name(&self, name: String) -> Option<Clan> {
+        let id = self.state.clan_names.get(&name.trim().to_lowercase()).await.ok().flatten()?;
+        self.state.clans.get(&id).await.ok().flatten()
+    }
+
+    /// Get clans ranked by aggregated XP
+    async fn clans(&self, limit: Option<u32>) -> Vec<Clan> {
+        let mut result = Vec::new();
+        let next_id = *self.state.next_clan_id.get();
+
+        for id in 1..next_id {
+            if let Ok(Some(clan)) = self.state.clans.get(&id).await {
+                result.push(clan);
+            }
+        }
+
+        result.sort_by_key(|c| std::cmp::Reverse(c.total_xp));
+        result.truncate(limit.unwrap_or(100) as usize);
+        result
+    }
+
+    /// Get clan roster
+    async fn clan_members(&self, clan_id: u64) -> Vec<ClanMember> {
+        self.state.clan_members.get(&clan_id).await.ok().flatten()
+            .unwrap_or_default()
+    }
+
+    /// Get the clan a player belongs to
+    async fn player_clan(&self, owner: String) -> Option<Clan> {
+        let wallet = parse_wallet_address(&owner)?;
+        let id = self.state.wallet_to_clan.get(&wallet).await.ok().flatten()?;
+        self.state.clans.get(&id).await.ok().flatten()
+    }
+
+    /// Get clans that invited a player
+    async fn clan_invites(&self, owner: String) -> Vec<Clan> {
+        let wallet = match parse_wallet_address(&owner) {
+            Some(w) => w,
+            None => return Vec::new(),
+        };
+        let ids = self.state.clan_invites.get(&wallet).await.ok().flatten()
+            .unwrap_or_default();
+
+        let mut result = Vec::new();
+        for id in ids {
+            if let Ok(Some(clan)) = self.state.clans.get(&id).await {
+                result.push(clan);
+            }
+        }
+        result
+    }
+
+    // ===== Duel Queries =====
+
+    /// Get duel by ID
+    async fn duel(&self, id: u64) -> Option<Duel> {
+        self.state.duels.get(&id).await.ok().flatten()
+    }
+
+    /// Get a player's duels (as challenger or opponent), newest first
+    async fn duels(&self, owner: String, status: Option<DuelStatus>) -> Vec<Duel> {
+        let wallet = match parse_wallet_address(&owner) {
+            Some(w) => w,
+            None => return Vec::new(),
+        };
+        let ids = self.state.player_duels.get(&wallet).await.ok().flatten()
+            .unwrap_or_default();
+
+        let mut result = Vec::new();
+        for id in ids.iter().rev() {
+            if let Ok(Some(duel)) = self.state.duels.get(id).await {
+                if status.is_none() || status == Some(duel.status) {
+                    result.push(duel);
+                }
+            }
+        }
+        result
+    }
+
+    /// Get open lobby duels anyone can accept
+    async fn open_duels(&self, limit: Option<u32>) -> Vec<Duel> {
+        let limit = limit.unwrap_or(20) as usize;
+        let ids = self.state.open_duels.get();
+
+        let mut result = Vec::new();
+        for id in ids.iter().take(limit) {
+            if let Ok(Some(duel)) = self.state.duels.get(id).await {
+                result.push(duel);
+            }
+        }
+        result
+    }
+
+    // ===== Achievement Queries =====
+
+    /// Get the full achievement catalogue
+    async fn achievement_catalogue(&self) -> Vec<AchievementInfo> {
+        ACHIEVEMENTS.iter()
+            .map(|definition| AchievementInfo {
+                id: definition.id,
+                name: definition.name.to_string(),
+                description: definition.description.to_string(),
+            })
+            .collect()
+    }
+
+    /// Get all achievements unlocked by a player
+    async fn achievements(&self, owner: String) -> Vec<PlayerAchievement> {
+        let wallet = match parse_wallet_address(&owner) {
+            Some(w) => w,
+            None => return Vec::new(),
+        };
+
+        let mut unlocked = Vec::new();
+        for definition in ACHIEVEMENTS {
+            let key = (wallet, definition.id);
+            if let Ok(Some(achievement)) = self.state.achievements.get(&key).await {
+                unlocked.push(achievement);
+            }
+        }
+
+        // Sort by unlock time (oldest first)
+        unlocked.sort_by_key(|a| a.unlocked_at);
+        unlocked
+    }
 }
 
 // ============================================
@@ -279,53 +745,231 @@ impl MutationRoot {
         true
     }
 
-    /// Submit a game run to a tournament
+    /// Submit a game run to a tournament (ticket_nonce = the nonce given to startRun)
     /// Schedules the operation for execution in the next block
     /// Returns true when operation is scheduled successfully
-    async fn submit_run(
-        &self,
-        tournament_id: u64,
-        time_ms: u64,
-        score: u64,
-        coins: u32,
-        deaths: u32,
-        completed: bool,
-    ) -> bool {
-        let operation = Operation::SubmitRun {
-            tournament_id,
-            time_ms,
-            score,
-            coins,
-            deaths,
-            completed,
+    async fn submit_run(&self, run: RunSubmissionInput) -> bool {
+        let Some(run) = parse_run_submission(run) else {
+            return false;
         };
+
+        let operation = Operation::SubmitRun { run };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Submit up to MAX_BATCH_RUNS runs for one tournament in a single operation
+    /// Each run gets its own receipt (see runReceipts)
+    /// Returns true when operation is scheduled successfully
+    async fn submit_runs(&self, runs: Vec<RunSubmissionInput>) -> bool {
+        if runs.is_empty() || runs.len() > MAX_BATCH_RUNS {
+            return false;
+        }
+        let Some(submissions) = runs.into_iter().map(parse_run_submission).collect::<Option<Vec<_>>>() else {
+            return false;
+        };
+        self.runtime.schedule_operation(&Operation::SubmitRuns { runs: submissions });
+        true
+    }
+
+    /// Start a run and get a ticket (nonce = fresh random u64 chosen by the client)
+    /// Returns true when operation is scheduled successfully
+    async fn start_run(&self, tournament_id: u64, nonce: u64) -> bool {
+        let operation = Operation::StartRun { tournament_id, nonce };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Commit to a run before playing (commitment = 32-byte hex hash)
+    /// Returns true when operation is scheduled successfully
+    async fn commit_run(&self, tournament_id: u64, commitment_hex: String) -> bool {
+        let commitment: [u8; 32] = match parse_hex_bytes(&commitment_hex).map(|b| b.try_into()) {
+            Some(Ok(c)) => c,
+            _ => return false,
+        };
+
+        let operation = Operation::CommitRun { tournament_id, commitment };
         self.runtime.schedule_operation(&operation);
         true
     }
 
     /// Create a new tournament (admin)
+    /// Omit seed_commitment_hex to derive the seed from chain entropy alone;
+    /// reward_percentages defaults to 40/25/15/12/8
     /// Returns true when operation is scheduled successfully
-    async fn create_tournament(
-        &self,
-        title: String,
-        description: String,
-        maze_seed: String,
-        difficulty: Difficulty,
-        duration_days: u64,
-        xp_reward_pool: u64,
-    ) -> bool {
+    async fn create_tournament(&self, tournament: TournamentInput) -> bool {
+        let seed_commitment: Option<[u8; 32]> = match tournament.seed_commitment_hex {
+            Some(hex) => match parse_hex_bytes(&hex).map(|b| b.try_into()) {
+                Some(Ok(c)) => Some(c),
+                _ => return false,
+            },
+            None => None,
+        };
+
+        let difficulty = tournament.difficulty;
         let operation = Operation::CreateTournament {
-            title,
-            description,
-            maze_seed,
+            title: tournament.title,
+            description: tournament.description,
+            seed_commitment,
             difficulty,
-            duration_days,
-            xp_reward_pool,
+            duration_days: tournament.duration_days,
+            xp_reward_pool: tournament.xp_reward_pool,
+            team_mode: tournament.team_mode.unwrap_or(TeamMode::Individual),
+            team_best_n: tournament.team_best_n.unwrap_or(3),
+            commit_reveal_required: tournament.commit_reveal_required.unwrap_or(false),
+            seed_mode: tournament.seed_mode.unwrap_or(SeedMode::Shared),
+            maze_config: tournament.maze_config.unwrap_or_else(|| difficulty.default_maze_config()),
+            reward_percentages: tournament.reward_percentages.unwrap_or_else(|| DEFAULT_REWARD_PERCENTAGES.to_vec()),
         };
         self.runtime.schedule_operation(&operation);
         true
     }
 
+    /// Register a recurring tournament template (hub chain only)
+    /// Returns true when operation is scheduled successfully
+    async fn create_template(&self, template: TemplateInput) -> bool {
+        let operation = Operation::CreateTemplate { template };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Pause or resume a template (hub chain only)
+    /// Returns true when operation is scheduled successfully
+    async fn set_template_active(&self, template_id: u64, active: bool) -> bool {
+        let operation = Operation::SetTemplateActive { template_id, active };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Finalize expired template instances and spawn the next ones (anyone)
+    /// Returns true when operation is scheduled successfully
+    async fn rotate_tournaments(&self) -> bool {
+        let operation = Operation::RotateTournaments;
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Amend an active tournament (organizer or hub chain)
+    /// Returns true when operation is scheduled successfully
+    async fn update_tournament(&self, tournament_id: u64, update: TournamentUpdate) -> bool {
+        let operation = Operation::UpdateTournament { tournament_id, update };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Cancel an active tournament (organizer or hub chain); XP is kept unless xp_policy = REVERT
+    /// Returns true when operation is scheduled successfully
+    async fn cancel_tournament(&self, tournament_id: u64, xp_policy: Option<CancelXpPolicy>) -> bool {
+        let operation = Operation::CancelTournament {
+            tournament_id,
+            xp_policy: xp_policy.unwrap_or(CancelXpPolicy::Keep),
+        };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Reveal the organizer secret committed at tournament creation
+    /// Returns true when operation is scheduled successfully
+    async fn reveal_tournament_seed(&self, tournament_id: u64, secret_hex: String) -> bool {
+        let secret = match parse_hex_bytes(&secret_hex) {
+            Some(s) => s,
+            None => return false,
+        };
+
+        let operation = Operation::RevealTournamentSeed { tournament_id, secret };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Merge the deprecated active_tournament register into the tournaments map (one-shot)
+    /// Returns true when operation is scheduled successfully
+    async fn reconcile_tournaments(&self) -> bool {
+        let operation = Operation::ReconcileTournaments;
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Rewrite up to `limit` stored records in the current schema layout (default 100)
+    /// Call repeatedly until `schemaVersion` reports the current version
+    /// Returns true when operation is scheduled successfully
+    async fn migrate(&self, limit: Option<u32>) -> bool {
+        let operation = Operation::Migrate { limit: limit.unwrap_or(100) };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Have the hub keep this chain's copy of the signer's profile, tournament and rank current
+    /// Returns true when operation is scheduled successfully
+    async fn subscribe_player_snapshots(&self, tournament_id: Option<u64>) -> bool {
+        let operation = Operation::SubscribePlayerSnapshots { tournament_id };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Stop snapshot pushes to this chain
+    /// Returns true when operation is scheduled successfully
+    async fn unsubscribe_player_snapshots(&self) -> bool {
+        let operation = Operation::UnsubscribePlayerSnapshots;
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Have the tournament's chain push leaderboard changes to this chain
+    /// Returns true when operation is scheduled successfully
+    async fn subscribe_leaderboard(&self, tournament_id: u64) -> bool {
+        let operation = Operation::SubscribeLeaderboard { tournament_id };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Stop leaderboard pushes to this chain
+    /// Returns true when operation is scheduled successfully
+    async fn unsubscribe_leaderboard(&self, tournament_id: u64) -> bool {
+        let operation = Operation::UnsubscribeLeaderboard { tournament_id };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Add a shard chain for new tournaments (hub chain only)
+    /// Returns true when operation is scheduled successfully
+    async fn add_shard(&self, chain_id: String) -> bool {
+        let operation = Operation::AddShard { chain_id };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Stop assigning new tournaments to a shard (hub chain only)
+    /// Returns true when operation is scheduled successfully
+    async fn remove_shard(&self, chain_id: String) -> bool {
+        let operation = Operation::RemoveShard { chain_id };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Ask the hub which chain hosts a tournament; the answer is cached on this chain
+    /// Returns true when operation is scheduled successfully
+    async fn locate_tournament(&self, tournament_id: u64) -> bool {
+        let operation = Operation::LocateTournament { tournament_id };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Move the hub to another chain (hub chain only)
+    /// Returns true when operation is scheduled successfully
+    async fn migrate_hub(&self, hub_chain_id: String) -> bool {
+        let operation = Operation::MigrateHub { hub_chain_id };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Finalize up to `limit` overdue tournaments (default 10)
+    /// Returns true when operation is scheduled successfully
+    async fn finalize_expired(&self, limit: Option<u32>) -> bool {
+        let operation = Operation::FinalizeExpired { limit: limit.unwrap_or(10) };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
     /// End a tournament (admin)
     /// Returns true when operation is scheduled successfully
     async fn end_tournament(&self, tournament_id: u64) -> bool {
@@ -342,6 +986,131 @@ impl MutationRoot {
         true
     }
     
+    /// Start a new season (admin)
+    /// Returns true when operation is scheduled successfully
+    async fn create_season(&self, title: String, duration_days: u64, xp_reward_pool: u64) -> bool {
+        let operation = Operation::CreateSeason {
+            title,
+            duration_days,
+            xp_reward_pool,
+        };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// End a season
+    /// Returns true when operation is scheduled successfully
+    async fn end_season(&self, season_id: u64) -> bool {
+        let operation = Operation::EndSeason { season_id };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Claim season reward
+    /// Returns true when operation is scheduled successfully
+    async fn claim_season_reward(&self, season_id: u64) -> bool {
+        let operation = Operation::ClaimSeasonReward { season_id };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Create a clan
+    /// Returns true when operation is scheduled successfully
+    async fn create_clan(&self, name: String, tag: String) -> bool {
+        let operation = Operation::CreateClan { name, tag };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Invite a wallet (hex) to your clan
+    /// Returns true when operation is scheduled successfully
+    async fn invite_to_clan(&self, invitee: String) -> bool {
+        let invitee = match parse_wallet_address(&invitee) {
+            Some(w) => w,
+            None => return false,
+        };
+        let operation = Operation::InviteToClan { invitee };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Join a clan you were invited to
+    /// Returns true when operation is scheduled successfully
+    async fn join_clan(&self, clan_id: u64) -> bool {
+        let operation = Operation::JoinClan { clan_id };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Leave your clan
+    /// Returns true when operation is scheduled successfully
+    async fn leave_clan(&self) -> bool {
+        let operation = Operation::LeaveClan;
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Change a clan member's role (wallet hex)
+    /// Returns true when operation is scheduled successfully
+    async fn set_clan_role(&self, member: String, role: ClanRole) -> bool {
+        let member = match parse_wallet_address(&member) {
+            Some(w) => w,
+            None => return false,
+        };
+        let operation = Operation::SetClanRole { member, role };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Challenge a wallet (hex) to a duel, or open a lobby when opponent is omitted
+    /// Returns true when operation is scheduled successfully
+    async fn create_duel(
+        &self,
+        opponent: Option<String>,
+        difficulty: Difficulty,
+        duration_hours: u64,
+    ) -> bool {
+        let opponent = match opponent {
+            Some(owner) => match parse_wallet_address(&owner) {
+                Some(wallet) => Some(wallet),
+                None => return false,
+            },
+            None => None,
+        };
+
+        let operation = Operation::CreateDuel {
+            opponent,
+            difficulty,
+            duration_hours,
+        };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Accept a duel
+    /// Returns true when operation is scheduled successfully
+    async fn accept_duel(&self, duel_id: u64) -> bool {
+        let operation = Operation::AcceptDuel { duel_id };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Submit your run for a duel
+    /// Returns true when operation is scheduled successfully
+    async fn submit_duel_run(&self, run: DuelRunSubmission) -> bool {
+        let operation = Operation::SubmitDuelRun { run };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
+    /// Settle a duel after its deadline
+    /// Returns true when operation is scheduled successfully
+    async fn resolve_duel(&self, duel_id: u64) -> bool {
+        let operation = Operation::ResolveDuel { duel_id };
+        self.runtime.schedule_operation(&operation);
+        true
+    }
+
     /// Bootstrap tournament #1 (creates if doesn't exist)
     /// This is idempotent - safe to call multiple times
     /// Returns true when operation is scheduled successfully
@@ -352,6 +1121,113 @@ impl MutationRoot {
     }
 }
 
+// ============================================
+// SUBSCRIPTION ROOT
+// ============================================
+// A service instance answers a single request, so a subscription can't stay
+// open here: each field yields one update past the client's cursor and ends.
+// Frontends listen to the node service's `notifications(chainId)` stream and
+// re-send the subscription (with the returned cursor) on every new block,
+// instead of polling on a timer. Send one field per subscription.
+
+struct SubscriptionRoot {
+    state: Arc<LabyrinthState>,
+}
+
+/// Runs scanned per update (a later update continues from `cursor`)
+const RUN_SCAN_WINDOW: u64 = 500;
+
+/// New runs in a tournament
+#[derive(SimpleObject)]
+struct RunsUpdate {
+    tournament_id: u64,
+    runs: Vec<GameRun>,                 // Oldest first
+    cursor: u64,                        // Pass as afterRunId next time
+}
+
+/// A tournament's leaderboard, if it changed since the client's cursor
+#[derive(SimpleObject)]
+struct LeaderboardUpdate {
+    tournament_id: u64,
+    changed: bool,
+    entries: Vec<LeaderboardEntry>,     // Empty when unchanged
+    version: u64,                       // Pass as `afterVersion` next time (0 = never changed)
+}
+
+/// Rewards a wallet can claim on this chain
+#[derive(SimpleObject)]
+struct RewardsUpdate {
+    rewards: Vec<TournamentReward>,     // Unclaimed only
+    total_xp: u64,
+}
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// Runs in a tournament with IDs above `after_run_id`
+    /// (default: starting from the last 100 runs on this chain)
+    async fn new_runs(&self, tournament_id: u64, after_run_id: Option<u64>) -> impl Stream<Item = RunsUpdate> {
+        let next_id = *self.state.next_run_id.get();
+        let first = match after_run_id {
+            Some(after) => after.saturating_add(1),
+            None => std::cmp::max(next_id.saturating_sub(100), 1),
+        };
+        let end = std::cmp::min(next_id, first.saturating_add(RUN_SCAN_WINDOW));
+
+        let mut runs = Vec::new();
+        for id in first..end {
+            if let Ok(Some(run)) = self.state.runs.get(&id).await {
+                let run = run.into_inner();
+                if run.tournament_id == tournament_id {
+                    runs.push(run);
+                }
+            }
+        }
+        // Never move the cursor back, even when nothing was scanned
+        let cursor = std::cmp::max(end.saturating_sub(1), after_run_id.unwrap_or_default());
+        stream::once(future::ready(RunsUpdate { tournament_id, runs, cursor }))
+    }
+
+    /// A tournament's leaderboard when its version moved past `after_version` (always, without a cursor)
+    async fn leaderboard_updates(
+        &self,
+        tournament_id: u64,
+        after_version: Option<u64>,
+        limit: Option<u32>,
+    ) -> impl Stream<Item = LeaderboardUpdate> {
+        let version = self.state.leaderboard_versions.get(&tournament_id).await.ok().flatten()
+            .unwrap_or_default();
+        let changed = after_version.is_none_or(|after| version > after);
+        let entries = if changed {
+            let limit = limit.unwrap_or(100) as usize;
+            self.state.leaderboards.get(&tournament_id).await.ok().flatten()
+                .unwrap_or_default()
+                .into_iter()
+                .take(limit)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        stream::once(future::ready(LeaderboardUpdate { tournament_id, changed, entries, version }))
+    }
+
+    /// Unclaimed tournament rewards for a wallet (hex address)
+    async fn rewards_available(&self, owner: String) -> impl Stream<Item = RewardsUpdate> {
+        let mut rewards = Vec::new();
+        if let Some(wallet) = parse_wallet_address(&owner) {
+            let next_id = *self.state.next_tournament_id.get();
+            for tournament_id in 1..next_id {
+                if let Ok(Some(reward)) = self.state.rewards.get(&(tournament_id, wallet)).await {
+                    if !reward.claimed {
+                        rewards.push(reward);
+                    }
+                }
+            }
+        }
+        let total_xp = rewards.iter().map(|r| r.xp_amount).sum();
+        stream::once(future::ready(RewardsUpdate { rewards, total_xp }))
+    }
+}
+
 // ============================================
 // HELPERS
 // ============================================
@@ -373,6 +1249,39 @@ fn parse_wallet_address(s: &str) -> Option<[u8; 20]> {
     Some(wallet)
 }
 
+/// Parse hex bytes (0x... or raw hex)
+fn parse_hex_bytes(s: &str) -> Option<Vec<u8>> {
+    hex::decode(s.trim_start_matches("0x")).ok()
+}
+
+/// Decode a run input's hex fields (reveal moves and salt must come together)
+fn parse_run_submission(run: RunSubmissionInput) -> Option<RunSubmission> {
+    let ghost = match run.ghost_hex {
+        Some(data) => Some(parse_hex_bytes(&data)?),
+        None => None,
+    };
+    let reveal = match (run.reveal_moves_hex, run.reveal_salt_hex) {
+        (Some(moves), Some(salt)) => Some(RunReveal {
+            moves: parse_hex_bytes(&moves)?,
+            salt: parse_hex_bytes(&salt)?,
+        }),
+        (None, None) => None,
+        _ => return None,
+    };
+    Some(RunSubmission {
+        tournament_id: run.tournament_id,
+        time_ms: run.time_ms,
+        score: run.score,
+        coins: run.coins,
+        deaths: run.deaths,
+        completed: run.completed,
+        splits: run.splits.unwrap_or_default(),
+        ghost,
+        reveal,
+        ticket_nonce: run.ticket_nonce,
+    })
+}
+
 /// Parse account owner from various formats
 fn parse_account_owner(s: &str) -> Option<AccountOwner> {
     // Try Address20 (40 hex chars, possibly with 0x prefix)
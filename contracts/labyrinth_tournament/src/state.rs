@@ -1,9 +1,14 @@
 //! Labyrinth Legends - Simplified Tournament State
 //! Optimized for tournament-first architecture
 
+use linera_sdk::linera_base_types::{ChainId, Timestamp};
 use linera_sdk::views::{linera_views, MapView, RegisterView, RootView, ViewStorageContext};
 use labyrinth_tournament::{
-    Tournament, Player, GameRun, TournamentPlayer, LeaderboardEntry, TournamentReward
+    Tournament, Player, GameRun, TournamentPlayer, LeaderboardEntry, TournamentReward,
+    AchievementId, PlayerAchievement, Season, SeasonStanding, SeasonReward, RatingEntry,
+    Duel, Clan, ClanMember, TeamLeaderboardEntry, SplitLeaderboardEntry, Ghost, RunCommitment,
+    RunTicket, TournamentAmendment, TournamentTemplate, RunReceipt, ClanXpCredit,
+    schema::{MigrationCursor, Versioned},
 };
 
 /// Main application state - tournament-focused
@@ -26,22 +31,22 @@ pub struct LabyrinthState {
 
     // ===== Tournaments =====
     /// All tournaments by ID (for historical lookup)
-    pub tournaments: MapView<u64, Tournament>,
-    /// Active tournament ID (only one active at a time for simplicity)
+    pub tournaments: MapView<u64, Versioned<Tournament>>,
+    /// Featured tournament ID (the default one clients open; others may also be Active)
     pub active_tournament_id: RegisterView<Option<u64>>,
-    /// DIRECT ACCESS: The currently active tournament stored in a RegisterView
-    /// This allows direct mutation without MapView clone issues
-    pub active_tournament: RegisterView<Option<Tournament>>,
+    /// DEPRECATED: former copy of the active tournament, kept only so view keys don't shift
+    /// Always None once ReconcileTournaments has run - read `tournaments` instead
+    pub active_tournament: RegisterView<Option<Versioned<Tournament>>>,
 
     // ===== Players =====
     /// Global player profiles by wallet address
-    pub players: MapView<[u8; 20], Player>,
+    pub players: MapView<[u8; 20], Versioned<Player>>,
     /// Username -> wallet mapping (for lookup)
     pub username_to_wallet: MapView<String, [u8; 20]>,
 
     // ===== Tournament Players =====
     /// Tournament participants: (tournament_id, wallet) -> TournamentPlayer
-    pub tournament_players: MapView<(u64, [u8; 20]), TournamentPlayer>,
+    pub tournament_players: MapView<(u64, [u8; 20]), Versioned<TournamentPlayer>>,
 
     // ===== Leaderboards =====
     /// Tournament leaderboards: tournament_id -> Vec<LeaderboardEntry>
@@ -50,11 +55,149 @@ pub struct LabyrinthState {
 
     // ===== Runs =====
     /// All game runs by ID
-    pub runs: MapView<u64, GameRun>,
+    pub runs: MapView<u64, Versioned<GameRun>>,
     /// Recent run IDs (last 100 for activity feed)
     pub recent_runs: RegisterView<Vec<u64>>,
 
     // ===== Rewards =====
     /// Tournament rewards: (tournament_id, wallet) -> TournamentReward
     pub rewards: MapView<(u64, [u8; 20]), TournamentReward>,
+
+    // ===== Achievements =====
+    /// Unlocked achievements: (wallet, achievement) -> PlayerAchievement
+    pub achievements: MapView<([u8; 20], AchievementId), PlayerAchievement>,
+
+    // ===== Seasons =====
+    /// All seasons by ID (current and historical)
+    pub seasons: MapView<u64, Season>,
+    /// Next season ID (0 on deployments that predate seasons - treat as 1)
+    pub next_season_id: RegisterView<u64>,
+    /// Currently active season ID
+    pub active_season_id: RegisterView<Option<u64>>,
+    /// Season leaderboards: season_id -> Vec<SeasonStanding>
+    /// Sorted by points descending
+    pub season_standings: MapView<u64, Vec<SeasonStanding>>,
+    /// Season rewards: (season_id, wallet) -> SeasonReward
+    pub season_rewards: MapView<(u64, [u8; 20]), SeasonReward>,
+
+    // ===== Ratings =====
+    /// Global ratings leaderboard (top 100, sorted by rating descending)
+    pub rating_leaderboard: RegisterView<Vec<RatingEntry>>,
+
+    // ===== Duels =====
+    /// All duels by ID
+    pub duels: MapView<u64, Duel>,
+    /// Next duel ID (0 on deployments that predate duels - treat as 1)
+    pub next_duel_id: RegisterView<u64>,
+    /// Duel IDs per participant (challenger and opponent)
+    pub player_duels: MapView<[u8; 20], Vec<u64>>,
+    /// Open lobby duels waiting for any opponent
+    pub open_duels: RegisterView<Vec<u64>>,
+
+    // ===== Clans =====
+    /// All clans by ID
+    pub clans: MapView<u64, Clan>,
+    /// Next clan ID (0 on deployments that predate clans - treat as 1)
+    pub next_clan_id: RegisterView<u64>,
+    /// Lowercased clan name -> clan ID (names are unique)
+    pub clan_names: MapView<String, u64>,
+    /// Clan roster: clan_id -> members (join order)
+    pub clan_members: MapView<u64, Vec<ClanMember>>,
+    /// Wallet -> clan ID (a wallet belongs to at most one clan)
+    pub wallet_to_clan: MapView<[u8; 20], u64>,
+    /// Pending invites: wallet -> clan IDs
+    pub clan_invites: MapView<[u8; 20], Vec<u64>>,
+    /// Team leaderboards: tournament_id -> Vec<TeamLeaderboardEntry>
+    /// Sorted by score descending
+    pub team_leaderboards: MapView<u64, Vec<TeamLeaderboardEntry>>,
+
+    // ===== Stage Splits =====
+    /// Per-stage leaderboards: (tournament_id, stage) -> best stage times
+    pub stage_leaderboards: MapView<(u64, u32), Vec<SplitLeaderboardEntry>>,
+    /// Sum-of-best-splits leaderboards: tournament_id -> entries
+    pub sum_of_best_leaderboards: MapView<u64, Vec<SplitLeaderboardEntry>>,
+
+    // ===== Ghost Replays =====
+    /// Stored ghosts by run ID
+    pub ghosts: MapView<u64, Ghost>,
+    /// Run IDs with a stored ghost, per tournament (at most GHOST_LEADERBOARD_SLOTS)
+    pub tournament_ghosts: MapView<u64, Vec<u64>>,
+
+    // ===== Commit-Reveal =====
+    /// Pending run commitments: (tournament_id, wallet) -> RunCommitment
+    pub run_commitments: MapView<(u64, [u8; 20]), RunCommitment>,
+
+    // ===== Run Tickets =====
+    /// Issued tickets: (wallet, nonce) -> RunTicket
+    pub run_tickets: MapView<([u8; 20], u64), RunTicket>,
+    /// Next ticket ID (0 on deployments that predate tickets - treat as 1)
+    pub next_ticket_id: RegisterView<u64>,
+
+    // ===== Amendments =====
+    /// Audit trail: tournament_id -> amendments (oldest first)
+    pub tournament_amendments: MapView<u64, Vec<TournamentAmendment>>,
+
+    // ===== Templates =====
+    /// Recurring tournament templates by ID
+    pub templates: MapView<u64, TournamentTemplate>,
+    /// Next template ID (0 on deployments that predate templates - treat as 1)
+    pub next_template_id: RegisterView<u64>,
+
+    // ===== Schema =====
+    /// Storage schema version (0 on deployments that predate versioning, see schema.rs)
+    pub schema_version: RegisterView<u32>,
+    /// Progress of an in-flight Migrate batch
+    pub migration_cursor: RegisterView<MigrationCursor>,
+
+    // ===== Hub Routing =====
+    /// Hub migrations seen by this chain (0 = configured at instantiation)
+    pub hub_epoch: RegisterView<u32>,
+    /// Former hub chains; the hub rejects their messages
+    pub retired_hub_chains: MapView<ChainId, Timestamp>,
+    /// Chains that have messaged the hub (hub only): chain -> last message time
+    pub user_chains: MapView<ChainId, Timestamp>,
+
+    // ===== Delivery Receipts =====
+    /// Runs submitted from this chain and the hub's answer, by receipt ID
+    pub run_receipts: MapView<u64, RunReceipt>,
+    /// Next receipt ID (0 on chains that predate receipts - treat as 1)
+    pub next_receipt_id: RegisterView<u64>,
+
+    // ===== Player Snapshots =====
+    /// Chains receiving snapshots of a wallet (hub only)
+    pub snapshot_subscribers: MapView<[u8; 20], Vec<ChainId>>,
+    /// Cached leaderboard ranks pushed by the hub: (tournament_id, wallet) -> rank (user chains)
+    pub cached_ranks: MapView<(u64, [u8; 20]), u32>,
+    /// When the hub last pushed a wallet's snapshot (user chains)
+    pub snapshot_synced_at: MapView<[u8; 20], Timestamp>,
+
+    // ===== Shards =====
+    /// Shard chains taking new tournaments, in assignment order (hub only)
+    pub shard_chains: RegisterView<Vec<ChainId>>,
+    /// Tournament directory: tournament_id -> hosting chain (missing = the hub)
+    /// The hub's copy is authoritative; shards list what they host, user chains cache lookups
+    pub tournament_shards: MapView<u64, ChainId>,
+
+    // ===== Leaderboard Subscriptions =====
+    /// Chains following a tournament's leaderboard (tournament's chain only)
+    pub leaderboard_subscribers: MapView<u64, Vec<ChainId>>,
+    /// When the tournament's chain last pushed a leaderboard delta (user chains)
+    pub leaderboard_synced_at: MapView<u64, Timestamp>,
+    /// Bumped on every change to this chain's copy of a tournament's leaderboard (cursor for the service)
+    pub leaderboard_versions: MapView<u64, u64>,
+
+    // ===== Cancellation =====
+    /// Participant wallets per tournament (join order), so a tournament's players can be
+    /// visited without scanning every tournament's
+    pub tournament_participants: MapView<u64, Vec<[u8; 20]>>,
+    /// Clan XP credited by a tournament's runs: (tournament_id, wallet) -> credits per clan
+    pub tournament_clan_xp: MapView<(u64, [u8; 20]), Vec<ClanXpCredit>>,
+
+    // ===== Team Boards =====
+    /// Team-mode tournaments whose boards follow roster changes (ended ones are dropped lazily)
+    pub team_tournaments: RegisterView<Vec<u64>>,
+
+    // ===== Run Ticket Pruning =====
+    /// Nonces of each wallet's outstanding tickets; expired ones are pruned on the next issue
+    pub open_run_tickets: MapView<[u8; 20], Vec<u64>>,
 }
@@ -0,0 +1,116 @@
+//! Storage schema migration tests
+//! Fixtures in tests/fixtures/v1 were written by the baseline (schema version 1)
+//! contract (untagged values) and must keep decoding as the current records
+
+use labyrinth_tournament::{
+    schema::{Versioned, CURRENT_SCHEMA_VERSION, RECORD_TAG},
+    Difficulty, GameRun, Player, SeedMode, TeamMode, Tournament, TournamentPlayer,
+    TournamentStatus, DEFAULT_RATING, DEFAULT_REWARD_PERCENTAGES,
+};
+
+fn fixture(hex_bytes: &str) -> Vec<u8> {
+    hex::decode(hex_bytes.trim()).expect("fixture is hex")
+}
+
+/// Decode a stored value the way the views do
+fn stored<T: labyrinth_tournament::schema::VersionedRecord>(bytes: &[u8]) -> T {
+    bcs::from_bytes::<Versioned<T>>(bytes).unwrap().into_inner()
+}
+
+#[test]
+fn v1_tournament_upgrades_with_defaults() {
+    let bytes = fixture(include_str!("fixtures/v1/tournament.hex"));
+    let tournament: Tournament = stored(&bytes);
+
+    assert_eq!(tournament.id, 1);
+    assert_eq!(tournament.title, "Genesis Cup");
+    assert_eq!(tournament.maze_seed, "labyrinth_1700000000000000");
+    assert_eq!(tournament.difficulty, Difficulty::Medium);
+    assert_eq!(tournament.status, TournamentStatus::Active);
+    assert_eq!(tournament.participant_count, 3);
+    assert_eq!(tournament.total_runs, 7);
+    assert_eq!(tournament.xp_reward_pool, 10000);
+
+    assert_eq!(tournament.team_mode, TeamMode::Individual);
+    assert_eq!(tournament.seed_mode, SeedMode::Shared);
+    assert!(tournament.seed_revealed);
+    assert_eq!(tournament.maze_config.width, Difficulty::Medium.default_maze_config().width);
+    assert!(tournament.reference_path_length > 0);
+    assert_eq!(tournament.reward_percentages, DEFAULT_REWARD_PERCENTAGES.to_vec());
+    assert_eq!(tournament.organizer, None);
+    assert_eq!(tournament.template_id, None);
+}
+
+#[test]
+fn v1_player_records_upgrade() {
+    let player: Player = stored(&fixture(include_str!("fixtures/v1/player.hex")));
+    assert_eq!(player.username, "runner");
+    assert_eq!(player.total_xp, 420);
+    assert_eq!(player.best_time_ms, Some(45_250));
+    assert_eq!(player.rating, DEFAULT_RATING);
+    assert!(player.rating_provisional);
+
+    let entry: TournamentPlayer =
+        stored(&fixture(include_str!("fixtures/v1/tournament_player.hex")));
+    assert_eq!(entry.best_time_ms, 45_250);
+    assert_eq!(entry.total_runs, 4);
+    assert!(entry.best_splits.is_empty());
+}
+
+#[test]
+fn v1_run_ranks_by_raw_time() {
+    let run: GameRun = stored(&fixture(include_str!("fixtures/v1/game_run.hex")));
+    assert_eq!(run.id, 7);
+    assert_eq!(run.time_ms, 45_250);
+    assert_eq!(run.ranked_time_ms, 45_250);
+    assert!(run.completed);
+    assert!(run.splits.is_empty());
+}
+
+#[test]
+fn v1_register_copy_upgrades() {
+    // The deprecated active_tournament register stores an optional tournament
+    let bytes = fixture(include_str!("fixtures/v1/active_tournament.hex"));
+    let tournament: Option<Versioned<Tournament>> = bcs::from_bytes(&bytes).unwrap();
+    let tournament = tournament.unwrap();
+    assert_eq!(tournament.version(), 1);
+    assert_eq!(tournament.get().title, "Genesis Cup");
+
+    let rewritten = bcs::to_bytes(&Some(Versioned::new(tournament.into_inner()))).unwrap();
+    let reloaded: Option<Versioned<Tournament>> = bcs::from_bytes(&rewritten).unwrap();
+    assert_eq!(reloaded.map(|t| t.version()), Some(CURRENT_SCHEMA_VERSION));
+}
+
+#[test]
+fn rewritten_records_use_current_layout() {
+    let bytes = fixture(include_str!("fixtures/v1/tournament.hex"));
+    let upgraded = Versioned::<Tournament>::decode(&bytes).unwrap();
+    assert_eq!(upgraded.version(), 1);
+
+    let rewritten = bcs::to_bytes(&Versioned::new(upgraded.into_inner())).unwrap();
+    assert!(rewritten.starts_with(&RECORD_TAG));
+    let reloaded = Versioned::<Tournament>::decode(&rewritten).unwrap();
+    assert_eq!(reloaded.version(), CURRENT_SCHEMA_VERSION);
+    assert_eq!(bcs::to_bytes(&reloaded).unwrap(), rewritten);
+
+    // The record itself nests like any other value (e.g. inside messages)
+    let tournament = reloaded.into_inner();
+    let nested: (u64, Tournament) = bcs::from_bytes(&bcs::to_bytes(&(9u64, &tournament)).unwrap()).unwrap();
+    assert_eq!(nested.1.title, tournament.title);
+
+    let run_bytes = fixture(include_str!("fixtures/v1/game_run.hex"));
+    let run = Versioned::<GameRun>::decode(&run_bytes).unwrap().into_inner();
+    let reloaded = Versioned::<GameRun>::decode(&bcs::to_bytes(&Versioned::new(run)).unwrap()).unwrap();
+    assert_eq!(reloaded.version(), CURRENT_SCHEMA_VERSION);
+}
+
+#[test]
+fn corrupt_records_are_rejected() {
+    let mut bytes = fixture(include_str!("fixtures/v1/player.hex"));
+    bytes.truncate(bytes.len() - 3);
+    assert!(bcs::from_bytes::<Versioned<Player>>(&bytes).is_err());
+
+    let mut bytes = fixture(include_str!("fixtures/v1/player.hex"));
+    bytes.push(0);
+    assert!(bcs::from_bytes::<Versioned<Player>>(&bytes).is_err());
+}
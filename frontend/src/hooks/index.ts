@@ -4,3 +4,4 @@
 
 export { useLineraConnection, type LineraConnectionState } from './useLineraConnection';
 export { useLabyrinth, type LabyrinthState } from './useLabyrinth';
+export { useChainSubscription } from './useChainSubscription';
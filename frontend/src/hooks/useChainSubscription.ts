@@ -0,0 +1,66 @@
+/**
+ * useChainSubscription - Follow a service subscription through block notifications
+ *
+ * A Linera service answers one request and exits, so each subscription field
+ * returns a single update past the cursor it was given. This hook sends the
+ * subscription once on connect and again on every new block, passing along the
+ * variables `onUpdate` derives from the last answer (usually the new cursor).
+ * It replaces polling the same data on a timer.
+ */
+
+import { useEffect, useRef } from 'react';
+import { lineraAdapter } from '../lib/linera';
+
+export function useChainSubscription<T>(
+  document: string,
+  variables: Record<string, unknown> | null, // null = disabled
+  onUpdate: (data: T) => Record<string, unknown>, // Returns the variables for the next request
+): void {
+  const onUpdateRef = useRef(onUpdate);
+  onUpdateRef.current = onUpdate;
+
+  // Restart from `variables` whenever they change (e.g. another tournament)
+  const key = variables ? JSON.stringify(variables) : null;
+
+  useEffect(() => {
+    if (key === null) return;
+
+    let next: Record<string, unknown> = JSON.parse(key);
+    let cancelled = false;
+    let inFlight = false;
+    let pending = false;
+
+    const send = async () => {
+      // One request at a time; a block that lands meanwhile triggers one more
+      if (inFlight) {
+        pending = true;
+        return;
+      }
+      inFlight = true;
+      try {
+        const data = await lineraAdapter.query<T>(document, next);
+        if (!cancelled) {
+          next = { ...next, ...onUpdateRef.current(data) };
+        }
+      } catch (error) {
+        console.warn('⚠️ Subscription update failed:', error);
+      } finally {
+        inFlight = false;
+        if (pending && !cancelled) {
+          pending = false;
+          send();
+        }
+      }
+    };
+
+    send();
+    const unsubscribe = lineraAdapter.subscribe(() => {
+      send();
+    });
+
+    return () => {
+      cancelled = true;
+      unsubscribe();
+    };
+  }, [document, key]);
+}
@@ -3,7 +3,7 @@
  * Tournament-First Architecture - All scoring on-chain
  */
 
-import { LINERA_CONFIG, LINERA_QUERIES, LINERA_MUTATIONS } from './config';
+import { LINERA_CONFIG, LINERA_QUERIES, LINERA_MUTATIONS, newRunTicketNonce } from './config';
 
 // ============================================
 // TYPES
@@ -27,7 +27,7 @@ export interface Tournament {
   difficulty: 'Easy' | 'Medium' | 'Hard' | 'Nightmare';
   startTime: number;
   endTime: number;
-  status: 'Active' | 'Ended';
+  status: 'Active' | 'Ended' | 'Cancelled';
   participantCount: number;
   totalRuns: number;
   xpRewardPool: number;
@@ -164,7 +164,7 @@ class LineraClient {
   /**
    * Get all tournaments (optionally filtered by status)
    */
-  async getTournaments(status?: 'Active' | 'Ended'): Promise<Tournament[]> {
+  async getTournaments(status?: 'Active' | 'Ended' | 'Cancelled'): Promise<Tournament[]> {
     const data = await this.query<{ tournaments: Tournament[] }>({
       query: LINERA_QUERIES.getTournaments,
       variables: { status },
@@ -246,6 +246,19 @@ class LineraClient {
   // GAME RUN OPERATIONS
   // ============================================
 
+  /**
+   * Request a run ticket for a tournament
+   * Returns the nonce to pass to submitRun as ticketNonce
+   */
+  async startRun(tournamentId: number): Promise<number> {
+    const nonce = newRunTicketNonce();
+    await this.mutate({
+      query: LINERA_MUTATIONS.startRun,
+      variables: { tournamentId, nonce },
+    });
+    return nonce;
+  }
+
   /**
    * Submit a game run to a tournament
    * This is the PRIMARY operation for gameplay
@@ -257,10 +270,11 @@ class LineraClient {
     coins: number;
     deaths: number;
     completed: boolean;
+    ticketNonce: number; // From startRun
   }) {
     return this.mutate({
       query: LINERA_MUTATIONS.submitRun,
-      variables: params,
+      variables: { run: params },
     });
   }
 
@@ -321,14 +335,14 @@ class LineraClient {
   async createTournament(params: {
     title: string;
     description: string;
-    mazeSeed: string;
+    seedCommitmentHex?: string; // sha3_256(secret) - reveal with revealTournamentSeed
     difficulty: 'Easy' | 'Medium' | 'Hard' | 'Nightmare';
     durationDays: number;
     xpRewardPool: number;
   }) {
     return this.mutate({
       query: LINERA_MUTATIONS.createTournament,
-      variables: params,
+      variables: { tournament: params },
     });
   }
 
@@ -250,10 +250,66 @@ export const LINERA_QUERIES = {
   `,
 };
 
+// ============================================
+// GRAPHQL SUBSCRIPTIONS - One update per request
+// ============================================
+// The service answers each subscription once with what changed past the cursor.
+// Re-send it on every block notification with the returned cursor (see useChainSubscription).
+
+export const LINERA_SUBSCRIPTIONS = {
+  // Runs in a tournament after a run ID cursor (omit afterRunId to start from the latest runs)
+  newRuns: `
+    subscription NewRuns($tournamentId: Int!, $afterRunId: Int) {
+      newRuns(tournamentId: $tournamentId, afterRunId: $afterRunId) {
+        tournamentId
+        cursor
+        runs {
+          id
+          walletAddress
+          username
+          tournamentId
+          timeMs
+          deaths
+          completed
+          xpEarned
+          createdAt
+        }
+      }
+    }
+  `,
+
+  // Tournament leaderboard, only when its version moved past afterVersion
+  leaderboardUpdates: `
+    subscription LeaderboardUpdates($tournamentId: Int!, $afterVersion: Int, $limit: Int) {
+      leaderboardUpdates(tournamentId: $tournamentId, afterVersion: $afterVersion, limit: $limit) {
+        tournamentId
+        changed
+        version
+        entries {
+          walletAddress
+          username
+          bestTimeMs
+          totalRuns
+          totalXp
+          rank
+        }
+      }
+    }
+  `,
+};
+
 // ============================================
 // GRAPHQL MUTATIONS - Tournament First
 // ============================================
 
+/**
+ * Fresh client-chosen nonce for startRun (kept below 2^53 so it survives JSON)
+ */
+export function newRunTicketNonce(): number {
+  const [high, low] = crypto.getRandomValues(new Uint32Array(2));
+  return (high & 0x1fffff) * 0x100000000 + low;
+}
+
 export const LINERA_MUTATIONS = {
   // ===== PLAYER OPERATIONS =====
   
@@ -267,25 +323,20 @@ export const LINERA_MUTATIONS = {
   
   // ===== GAME RUN OPERATIONS =====
   
+  // Request a run ticket before playing (nonce from newRunTicketNonce)
+  // The hub timestamps the ticket; submitRun must quote the same nonce
+  startRun: `
+    mutation StartRun($tournamentId: Int!, $nonce: Int!) {
+      startRun(tournamentId: $tournamentId, nonce: $nonce)
+    }
+  `,
+
   // Submit a game run to the active tournament
   // This is the MAIN operation - auto-registers player if needed
+  // run: { tournamentId, timeMs, score, coins, deaths, completed, ticketNonce }
   submitRun: `
-    mutation SubmitRun(
-      $tournamentId: Int!
-      $timeMs: Int!
-      $score: Int!
-      $coins: Int!
-      $deaths: Int!
-      $completed: Boolean!
-    ) {
-      submitRun(
-        tournamentId: $tournamentId
-        timeMs: $timeMs
-        score: $score
-        coins: $coins
-        deaths: $deaths
-        completed: $completed
-      )
+    mutation SubmitRun($run: RunSubmissionInput!) {
+      submitRun(run: $run)
     }
   `,
   
@@ -293,22 +344,8 @@ export const LINERA_MUTATIONS = {
   
   // Create a new tournament (admin only)
   createTournament: `
-    mutation CreateTournament(
-      $title: String!
-      $description: String!
-      $mazeSeed: String!
-      $difficulty: Difficulty!
-      $durationDays: Int!
-      $xpRewardPool: Int!
-    ) {
-      createTournament(
-        title: $title
-        description: $description
-        mazeSeed: $mazeSeed
-        difficulty: $difficulty
-        durationDays: $durationDays
-        xpRewardPool: $xpRewardPool
-      )
+    mutation CreateTournament($tournament: TournamentInput!) {
+      createTournament(tournament: $tournament)
     }
   `,
   
@@ -188,6 +188,7 @@ export function useLineraWallet() {
     coins: number;
     deaths: number;
     completed: boolean;
+    ticketNonce: number; // From lineraClient.startRun
   }) => {
     if (!state.address) {
       throw new Error('No wallet connected');
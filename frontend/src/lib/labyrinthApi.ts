@@ -23,6 +23,7 @@
  */
 
 import { lineraAdapter } from './linera';
+import { LINERA_MUTATIONS, newRunTicketNonce } from './chain/config';
 
 // API URL for backend
 const API_URL = import.meta.env.VITE_API_URL || 'http://localhost:3001';
@@ -55,25 +56,11 @@ const REGISTER_PLAYER = `
 
 // Submit run to tournament (PRIMARY operation)
 // This auto-registers player if needed, validates tournament window, calculates XP
-const SUBMIT_RUN = `
-  mutation SubmitRun(
-    $tournamentId: Int!
-    $timeMs: Int!
-    $score: Int!
-    $coins: Int!
-    $deaths: Int!
-    $completed: Boolean!
-  ) {
-    submitRun(
-      tournamentId: $tournamentId
-      timeMs: $timeMs
-      score: $score
-      coins: $coins
-      deaths: $deaths
-      completed: $completed
-    )
-  }
-`;
+// Needs the nonce of a ticket requested with startRun before the run began
+const SUBMIT_RUN = LINERA_MUTATIONS.submitRun;
+
+// Request a run ticket (call when the run starts)
+const START_RUN = LINERA_MUTATIONS.startRun;
 
 // Claim tournament reward after tournament ends
 const CLAIM_REWARD = `
@@ -355,6 +342,16 @@ class LabyrinthApiClass {
   // TOURNAMENT OPERATIONS (Blockchain)
   // ===========================================================================
 
+  /**
+   * Request a run ticket for a tournament (call when the run starts)
+   * @returns The nonce to pass to submitTournamentRun
+   */
+  async startTournamentRun(tournamentId: number): Promise<number> {
+    const nonce = newRunTicketNonce();
+    await lineraAdapter.mutate(START_RUN, { tournamentId, nonce });
+    return nonce;
+  }
+
   /**
    * Submit a run to a tournament
    * This is the PRIMARY blockchain operation - auto-registers player if needed
@@ -370,6 +367,7 @@ class LabyrinthApiClass {
    * @param coins - Coins collected
    * @param deaths - Number of deaths
    * @param completed - Whether the run was completed
+   * @param ticketNonce - Nonce returned by startTournamentRun for this run
    */
   async submitTournamentRun(
     tournamentId: number,
@@ -377,19 +375,23 @@ class LabyrinthApiClass {
     score: number,
     coins: number,
     deaths: number,
-    completed: boolean
+    completed: boolean,
+    ticketNonce: number
   ): Promise<{ runId: number; xpEarned: number; newBest: boolean; rank: number }> {
     console.log(`📤 Submitting run to tournament #${tournamentId}...`);
 
     const result = await lineraAdapter.mutate<{
       submitRun: { runId: number; xpEarned: number; newBest: boolean; rank: number } | string
     }>(SUBMIT_RUN, {
-      tournamentId,
-      timeMs: Math.floor(timeMs),
-      score,
-      coins,
-      deaths,
-      completed,
+      run: {
+        tournamentId,
+        timeMs: Math.floor(timeMs),
+        score,
+        coins,
+        deaths,
+        completed,
+        ticketNonce,
+      },
     });
 
     // Handle BCS-encoded response from contract
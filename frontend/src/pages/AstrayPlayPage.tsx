@@ -9,7 +9,7 @@ import { useWalletSigner, formatTime } from '../lib/wallet';
 import { useLineraConnection } from '../hooks';
 import { lineraAdapter } from '../lib/linera';
 import { Tournament } from '../lib/chain/client';
-import { LINERA_QUERIES, LINERA_MUTATIONS } from '../lib/chain/config';
+import { LINERA_QUERIES, LINERA_MUTATIONS, newRunTicketNonce } from '../lib/chain/config';
 
 // ═══════════════════════════════════════════════════════════════
 // TOURNAMENT-FIRST ARCHITECTURE
@@ -22,6 +22,7 @@ import { LINERA_QUERIES, LINERA_MUTATIONS } from '../lib/chain/config';
 // GraphQL operations
 const GET_PLAYER = LINERA_QUERIES.getPlayer;
 const REGISTER_PLAYER = LINERA_MUTATIONS.registerPlayer;
+const START_RUN = LINERA_MUTATIONS.startRun;
 const SUBMIT_RUN = LINERA_MUTATIONS.submitRun;
 
 // ═══════════════════════════════════════════════════════════════
@@ -137,6 +138,8 @@ export default function AstrayPlayPage() {
   const [gameScore, setGameScore] = useState(0);
   const [coinsCollected, setCoinsCollected] = useState(0);
   const [deathCount, setDeathCount] = useState(0);
+  // Ticket requested when the current tournament run started (null = none)
+  const runTicketRef = useRef<{ tournamentId: number; nonce: number } | null>(null);
 
   // ═══════════════════════════════════════════════════════════════
   // TOURNAMENT STATE - The active tournament we're playing in
@@ -538,6 +541,14 @@ export default function AstrayPlayPage() {
       return;
     }
     
+    // The hub only accepts runs that quote a ticket issued before they started
+    const ticket = runTicketRef.current;
+    runTicketRef.current = null;
+    if (!ticket || ticket.tournamentId !== tournamentId) {
+      console.warn('⚠️ No run ticket for this run - not submitting to blockchain');
+      return;
+    }
+
     setSubmittingToChain(true);
     try {
       console.log(`📤 Submitting run to tournament #${tournamentId}...`);
@@ -547,12 +558,15 @@ export default function AstrayPlayPage() {
       const result = await lineraAdapter.mutate(
         SUBMIT_RUN,
         {
-          tournamentId,
-          timeMs: Math.floor(time),
-          score,
-          coins,
-          deaths,
-          completed: true,
+          run: {
+            tournamentId,
+            timeMs: Math.floor(time),
+            score,
+            coins,
+            deaths,
+            completed: true,
+            ticketNonce: ticket.nonce,
+          },
         }
       );
       
@@ -579,6 +593,17 @@ export default function AstrayPlayPage() {
     }
   };
 
+  // Request a run ticket in the background - the hub timestamps it before the run ends
+  const requestRunTicket = () => {
+    runTicketRef.current = null;
+    if (!activeTournament || !isAppConnected) return;
+
+    const ticket = { tournamentId: activeTournament.id, nonce: newRunTicketNonce() };
+    runTicketRef.current = ticket;
+    lineraAdapter.mutate(START_RUN, { tournamentId: ticket.tournamentId, nonce: ticket.nonce })
+      .catch((error) => console.warn('⚠️ startRun may have failed:', error));
+  };
+
   // Handle play button - INSTANT gameplay, no blockchain wait
   const handlePlay = async () => {
     if (!primaryWallet) {
@@ -604,6 +629,8 @@ export default function AstrayPlayPage() {
       toast.success(`Playing in: ${activeTournament.title}`, { duration: 2000 });
     }
 
+    requestRunTicket();
+
     // START GAME IMMEDIATELY
     console.log('🎮 Starting game instantly!');
     setGameStarted(true);
@@ -644,6 +671,7 @@ export default function AstrayPlayPage() {
     setCoinsCollected(0);
     setDeathCount(0);
     resetGame();
+    requestRunTicket(); // The submitted run used up the previous ticket
   };
 
   // Handle manual score submit
@@ -1,12 +1,53 @@
 import { Link } from 'react-router-dom';
 import { motion } from 'framer-motion';
 import { Gamepad2, Trophy, Users, Zap, ChevronRight, Play, Flame, Shield, Sword, Crown } from 'lucide-react';
+import { useState, useCallback } from 'react';
 import { useQuery } from '@tanstack/react-query';
 import { getStats, getTournaments, getRecentRuns } from '../lib/api';
 import { formatTime, formatRelativeTime, getDifficultyColor } from '../lib/wallet';
-import type { Tournament, GameRun } from '../types';
+import { useLineraConnection, useChainSubscription } from '../hooks';
+import { lineraAdapter } from '../lib/linera';
+import { LINERA_QUERIES, LINERA_SUBSCRIPTIONS } from '../lib/chain/config';
+import type { Tournament, GameRun, Difficulty } from '../types';
+
+const RECENT_RUNS_SHOWN = 5;
+
+// Run as returned by the newRuns subscription
+interface ChainRun {
+  id: number;
+  walletAddress: number[];
+  username: string;
+  tournamentId: number;
+  timeMs: number;
+  deaths: number;
+  completed: boolean;
+  xpEarned: number;
+  createdAt: number | string; // Microseconds
+}
+
+interface NewRunsResponse {
+  newRuns: { tournamentId: number; cursor: number; runs: ChainRun[] };
+}
+
+function toGameRun(run: ChainRun, difficulty: Difficulty): GameRun {
+  return {
+    id: String(run.id),
+    walletAddress: '0x' + run.walletAddress.map(b => b.toString(16).padStart(2, '0')).join(''),
+    username: run.username,
+    mode: 'Tournament',
+    tournamentId: run.tournamentId,
+    difficulty,
+    levelReached: 0,
+    timeMs: run.timeMs,
+    deaths: run.deaths,
+    completed: run.completed,
+    xpEarned: run.xpEarned,
+    createdAt: new Date(Number(run.createdAt) / 1000).toISOString(),
+  };
+}
 
 export default function HomePage() {
+  const { isAppConnected } = useLineraConnection();
   const { data: stats } = useQuery({
     queryKey: ['stats'],
     queryFn: getStats,
@@ -18,10 +59,35 @@ export default function HomePage() {
     queryFn: () => getTournaments('Active'),
   });
 
-  const { data: recentRuns } = useQuery({
+  // Backend feed only while the chain is not connected
+  const { data: backendRuns } = useQuery({
     queryKey: ['recentRuns'],
-    queryFn: () => getRecentRuns(5),
+    queryFn: () => getRecentRuns(RECENT_RUNS_SHOWN),
+    enabled: !isAppConnected,
+  });
+
+  // On-chain feed: the featured tournament's runs, pushed on every new block
+  const { data: featured } = useQuery({
+    queryKey: ['chainActiveTournament'],
+    queryFn: () => lineraAdapter.query<{ activeTournament: { id: number; difficulty: Difficulty } | null }>(
+      LINERA_QUERIES.getActiveTournament
+    ).then((result) => result.activeTournament),
+    enabled: isAppConnected,
   });
+  const [chainRuns, setChainRuns] = useState<GameRun[] | null>(null);
+  const onNewRuns = useCallback((data: NewRunsResponse) => {
+    const difficulty = featured?.difficulty ?? 'Medium';
+    const fresh = data.newRuns.runs.map((run) => toGameRun(run, difficulty)).reverse();
+    setChainRuns((previous) => [...fresh, ...(previous ?? [])].slice(0, RECENT_RUNS_SHOWN));
+    return { afterRunId: data.newRuns.cursor };
+  }, [featured]);
+  useChainSubscription<NewRunsResponse>(
+    LINERA_SUBSCRIPTIONS.newRuns,
+    isAppConnected && featured ? { tournamentId: featured.id } : null,
+    onNewRuns,
+  );
+
+  const recentRuns = isAppConnected ? chainRuns : backendRuns;
 
   return (
     <div className="min-h-screen">
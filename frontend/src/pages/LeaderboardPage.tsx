@@ -1,4 +1,4 @@
-import { useState, useEffect } from 'react';
+import { useState, useCallback } from 'react';
 import { useQuery } from '@tanstack/react-query';
 import { motion } from 'framer-motion';
 import { Crown, Flame, Shield, Scroll } from 'lucide-react';
@@ -6,9 +6,9 @@ import clsx from 'clsx';
 
 import { getPracticeLeaderboard } from '../lib/api';
 import { useWalletSigner, formatTime, formatXP } from '../lib/wallet';
-import { useLineraConnection } from '../hooks';
+import { useLineraConnection, useChainSubscription } from '../hooks';
 import { lineraAdapter } from '../lib/linera';
-import { LINERA_QUERIES } from '../lib/chain/config';
+import { LINERA_QUERIES, LINERA_SUBSCRIPTIONS } from '../lib/chain/config';
 import type { LeaderboardEntry } from '../types';
 
 // Blockchain leaderboard entry type
@@ -21,6 +21,15 @@ interface BlockchainLeaderboardEntry {
   rank: number;
 }
 
+interface LeaderboardUpdatesResponse {
+  leaderboardUpdates: {
+    tournamentId: number;
+    changed: boolean;
+    version: number;
+    entries: BlockchainLeaderboardEntry[];
+  };
+}
+
 // Convert wallet bytes to hex string
 function bytesToHex(bytes: number[]): string {
   return '0x' + bytes.map(b => b.toString(16).padStart(2, '0')).join('');
@@ -32,66 +41,49 @@ export default function LeaderboardPage() {
   const currentAddress = getAddress();
   
   const [blockchainLeaderboard, setBlockchainLeaderboard] = useState<LeaderboardEntry[] | null>(null);
-  const [loadingBlockchain, setLoadingBlockchain] = useState(false);
-
-  // Fetch from blockchain first when connected
-  useEffect(() => {
-    async function fetchBlockchainLeaderboard() {
-      if (!isAppConnected) return;
-      
-      setLoadingBlockchain(true);
-      try {
-        console.log('📡 Fetching leaderboard from blockchain...');
-        
-        // Get active tournament first
-        const tournamentResult = await lineraAdapter.query<{ activeTournament: { id: number } | null }>(
-          LINERA_QUERIES.getActiveTournament
-        );
 
-        if (tournamentResult.activeTournament) {
-          const tournamentId = tournamentResult.activeTournament.id;
-          
-          // Get leaderboard for this tournament
-          const result = await lineraAdapter.query<{ leaderboard: BlockchainLeaderboardEntry[] }>(
-            LINERA_QUERIES.getLeaderboard,
-            { tournamentId, limit: 100 }
-          );
+  // Featured tournament on the blockchain
+  const { data: activeTournamentId, isLoading: loadingTournament } = useQuery({
+    queryKey: ['chainActiveTournamentId'],
+    queryFn: () => lineraAdapter.query<{ activeTournament: { id: number } | null }>(
+      LINERA_QUERIES.getActiveTournament
+    ).then((result) => result.activeTournament?.id ?? null),
+    enabled: isAppConnected,
+  });
 
-          if (result.leaderboard && result.leaderboard.length > 0) {
-            // Convert blockchain format to UI format
-            const converted: LeaderboardEntry[] = result.leaderboard.map((entry, index) => ({
-              rank: entry.rank || index + 1,
-              walletAddress: bytesToHex(entry.walletAddress),
-              username: entry.username,
-              bestTimeMs: entry.bestTimeMs,
-              totalRuns: entry.totalRuns,
-              totalXp: entry.totalXp,
-            }));
-            
-            setBlockchainLeaderboard(converted);
-            console.log('✅ Blockchain leaderboard loaded:', converted.length, 'entries');
-          }
-        }
-      } catch (error) {
-        console.warn('Blockchain leaderboard fetch failed:', error);
-      } finally {
-        setLoadingBlockchain(false);
-      }
+  // Its leaderboard, re-sent on every new block and only replaced when it changed
+  const onLeaderboardUpdate = useCallback((data: LeaderboardUpdatesResponse) => {
+    const update = data.leaderboardUpdates;
+    if (update.changed && update.entries.length > 0) {
+      // Convert blockchain format to UI format
+      const converted: LeaderboardEntry[] = update.entries.map((entry, index) => ({
+        rank: entry.rank || index + 1,
+        walletAddress: bytesToHex(entry.walletAddress),
+        username: entry.username,
+        bestTimeMs: entry.bestTimeMs,
+        totalRuns: entry.totalRuns,
+        totalXp: entry.totalXp,
+      }));
+      setBlockchainLeaderboard(converted);
     }
+    return { afterVersion: update.version };
+  }, []);
+  useChainSubscription<LeaderboardUpdatesResponse>(
+    LINERA_SUBSCRIPTIONS.leaderboardUpdates,
+    isAppConnected && activeTournamentId != null ? { tournamentId: activeTournamentId, limit: 100 } : null,
+    onLeaderboardUpdate,
+  );
 
-    fetchBlockchainLeaderboard();
-  }, [isAppConnected]);
-
-  // Backend fallback query
+  // Backend fallback while the chain is not connected
   const { data: backendLeaderboard, isLoading: isLoadingBackend } = useQuery({
     queryKey: ['practiceLeaderboard'],
     queryFn: () => getPracticeLeaderboard(100),
-    refetchInterval: 30000, // Refresh every 30s
+    enabled: !isAppConnected,
   });
 
   // Use blockchain data if available, fallback to backend
   const leaderboard = blockchainLeaderboard || backendLeaderboard;
-  const isLoading = loadingBlockchain || (!blockchainLeaderboard && isLoadingBackend);
+  const isLoading = (isAppConnected && loadingTournament) || (!blockchainLeaderboard && isLoadingBackend);
 
   // Find current user's rank
   const userEntry = leaderboard?.find(
@@ -1,7 +1,7 @@
 // API Types matching backend
 export type Difficulty = 'Easy' | 'Medium' | 'Hard' | 'Nightmare';
 export type GameMode = 'Practice' | 'Tournament';
-export type TournamentStatus = 'Upcoming' | 'Active' | 'Ended';
+export type TournamentStatus = 'Upcoming' | 'Active' | 'Ended' | 'Cancelled';
 export type Division = 'Bronze' | 'Silver' | 'Gold' | 'Platinum' | 'Diamond' | 'Champion';
 
 // ═══════════════════════════════════════════════════════════════